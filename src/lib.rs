@@ -17,12 +17,17 @@
 //!
 //! ```
 
-use std::path::Path;
-use std::{collections::HashMap, io::Read};
+use std::path::{Component, Path, PathBuf};
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    convert::Infallible,
+    io::Read,
+    str::FromStr,
+};
 
 use json_comments::StripComments;
-use regex::Regex;
-use serde::{Deserialize, Deserializer};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::Value;
 
 use thiserror::Error;
@@ -36,19 +41,177 @@ pub enum ConfigError {
     ParseError(#[from] serde_json::Error),
     #[error("Could not read file")]
     CouldNotFindFile(#[from] std::io::Error),
+    /// The file is not valid JSON, even allowing for comments and trailing commas.
+    ///
+    /// `line` and `column` are 1-based and refer to the original source, before comments were stripped.
+    #[error("Invalid JSON at line {line}, column {column}")]
+    InvalidJson {
+        line: usize,
+        column: usize,
+        /// The line of the original source containing the error.
+        snippet: String,
+        source: serde_json::Error,
+    },
+    /// The data given to [TsConfig::parse_reader] or [TsConfig::parse_bytes] is not valid UTF-8.
+    #[error("Configuration is not valid UTF-8")]
+    InvalidUtf8(#[from] std::str::Utf8Error),
+    /// Reading one of the files in an `extends` chain, or parsing the file being loaded, failed.
+    ///
+    /// `path` is the file at fault, and `source` holds the underlying error.
+    #[error("Could not load configuration file {}", .path.display())]
+    FileError {
+        path: PathBuf,
+        source: Box<ConfigError>,
+    },
+    /// A file named in `extends` exists but is not a valid configuration file.
+    ///
+    /// `path` is the base file at fault, and `source` holds the underlying error.
+    #[error("Invalid base configuration file {}", .path.display())]
+    InvalidExtends {
+        path: PathBuf,
+        source: Box<ConfigError>,
+    },
+    /// A configuration file inherits from itself, directly or through other files.
+    ///
+    /// Holds the files that make up the cycle, starting and ending with the same file.
+    #[error("Configuration files extend each other in a cycle")]
+    CircularExtends(Vec<PathBuf>),
+    /// Projects reference each other in a cycle, so there is no order to build them in.
+    ///
+    /// Holds the configuration files that make up the cycle, starting and ending with the same file.
+    #[error("Project references form a cycle")]
+    CircularReferences(Vec<PathBuf>),
+    /// [TsConfig::parse_str_strict] found an option it does not recognise.
+    ///
+    /// Holds the option's path in the file, such as `compilerOptions.strcit`.
+    #[error("Unknown option `{0}`")]
+    UnknownOption(String),
+    /// [TsConfig::parse_str_strict] found a key set twice in the same object.
+    ///
+    /// Holds the key's path in the file, such as `compilerOptions.strict`.
+    #[error("Duplicate key `{0}`")]
+    DuplicateKey(String),
+    /// [TsConfig::find_and_parse] found no tsconfig.json in the directory it started from or any of its parents.
+    #[error("Could not find a tsconfig.json in {} or any parent directory", .0.display())]
+    ConfigNotFound(PathBuf),
+    /// [set_option_in_source] was asked to set an option inside a value that is not an object.
+    ///
+    /// Holds the path of that value, such as `compilerOptions.paths`.
+    #[error("Cannot set options inside `{0}` because it is not an object")]
+    NotAnObject(String),
+}
+
+/// A combination of compiler options that `tsc` would reject, as reported by [TsConfig::validate].
+///
+/// The messages follow the wording of the corresponding TypeScript diagnostics.
+#[derive(Debug, Error, Clone, PartialEq)]
+pub enum ValidationError {
+    /// `composite` is set but `declaration` is explicitly disabled.
+    #[error("Composite projects may not disable declaration emit")]
+    CompositeWithoutDeclaration,
+    /// `composite` is set but `incremental` is explicitly disabled.
+    #[error("Composite projects may not disable incremental compilation")]
+    CompositeWithoutIncremental,
+    /// The named option only makes sense when declaration files are emitted.
+    #[error("Option '{0}' cannot be specified without specifying option 'declaration' or option 'composite'")]
+    RequiresDeclaration(&'static str),
+    /// `outFile` is combined with a module kind that cannot be bundled into a single file.
+    #[error("Only 'amd' and 'system' modules are supported alongside --outFile, not '{0}'")]
+    OutFileWithModule(Module),
+    /// Two options that cannot be set together are both enabled.
+    #[error("Option '{0}' cannot be specified with option '{1}'")]
+    ConflictingOptions(&'static str, &'static str),
 }
 
 /// The main struct representing a parsed .tsconfig file.
-#[derive(Deserialize, Debug, Clone)]
+///
+/// All top-level fields are public; a field is `None` when the key was absent from the file.
+///
+/// ## Example
+/// ```
+/// use tsconfig::TsConfig;
+///
+/// let json = r#"{"include": ["src/**/*", "types/*.d.ts"], "exclude": ["node_modules"]}"#;
+/// let config = TsConfig::parse_str(json).unwrap();
+///
+/// let include: &Vec<String> = config.include.as_ref().unwrap();
+/// for glob in include {
+///     assert!(glob.ends_with('*') || glob.ends_with(".d.ts"));
+/// }
+/// assert_eq!(config.files, None);
+/// ```
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct TsConfig {
+    /// The JSON schema the file declares for editors, usually `https://json.schemastore.org/tsconfig`.
+    #[serde(rename = "$schema", skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+    /// Whether editors such as Visual Studio should compile files when they are saved.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compile_on_save: Option<bool>,
+    /// Filenames or patterns that should be skipped when resolving `include`.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_string_or_list",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub exclude: Option<Vec<String>>,
-    pub extends: Option<String>,
+    /// Path, or since TypeScript 5.0 list of paths, to other configuration files to inherit from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extends: Option<Extends>,
+    /// An allowlist of files to include in the program.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_string_or_list",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub files: Option<Vec<String>>,
+    /// Filenames or patterns to include in the program, relative to the configuration file.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_string_or_list",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub include: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub references: Option<References>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub type_acquisition: Option<TypeAcquisition>,
+    /// Type redirections per TypeScript version range, as in a package.json. `tsc` does not read this from a
+    /// configuration file, but it is kept so that it survives a round trip.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub types_versions: Option<HashMap<String, HashMap<String, Vec<String>>>>,
+    /// How `tsc --watch` should watch files and directories.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub watch_options: Option<WatchOptions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub compiler_options: Option<CompilerOptions>,
+    /// Whether the configuration was loaded with [TsConfig::parse_jsconfig_file], and so describes a JavaScript
+    /// project. This is not part of the file itself.
+    #[serde(skip)]
+    pub is_jsconfig: bool,
+}
+
+/// Reads `files`, `include` or `exclude`, also accepting a single string in place of a list.
+///
+/// TypeScript itself requires a list, but some hand-written and generated files use a bare string.
+fn deserialize_string_or_list<'de, D>(deserializer: D) -> Result<Option<Vec<String>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum StringOrList {
+        String(String),
+        List(Vec<String>),
+    }
+
+    Ok(
+        Option::<StringOrList>::deserialize(deserializer)?.map(|value| match value {
+            StringOrList::String(string) => vec![string],
+            StringOrList::List(list) => list,
+        }),
+    )
 }
 
 impl TsConfig {
@@ -112,6 +275,172 @@ impl TsConfig {
         Ok(cfg)
     }
 
+    /// Finds the tsconfig.json that applies to `start_dir` and parses it, the way `tsc` and editors locate a project.
+    ///
+    /// `start_dir` and then each of its parent directories are searched in turn, and the first tsconfig.json found
+    /// is parsed with [TsConfig::parse_file]. Returns its path alongside the configuration, or
+    /// [ConfigError::ConfigNotFound] if no directory has one.
+    ///
+    /// ## Example
+    /// ```
+    /// use std::path::Path;
+    /// use tsconfig::TsConfig;
+    ///
+    /// let project_dir = Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap()).join("test/project");
+    /// let (path, config) = TsConfig::find_and_parse(&project_dir.join("src/components")).unwrap();
+    ///
+    /// assert_eq!(path, project_dir.join("tsconfig.json"));
+    /// assert_eq!(config.compiler_options.unwrap().out_dir.as_deref(), Some("dist"));
+    /// ```
+    pub fn find_and_parse(start_dir: &Path) -> Result<(PathBuf, TsConfig)> {
+        let path = start_dir
+            .ancestors()
+            .map(|dir| dir.join("tsconfig.json"))
+            .find(|path| path.is_file())
+            .ok_or_else(|| ConfigError::ConfigNotFound(start_dir.to_path_buf()))?;
+        let config = TsConfig::parse_file(&path)?;
+        Ok((path, config))
+    }
+
+    /// Parses a jsconfig.json file into a [TsConfig], applying the defaults TypeScript uses for JavaScript projects.
+    ///
+    /// A jsconfig.json is read exactly like a tsconfig.json, except that `allowJs`, `allowSyntheticDefaultImports`,
+    /// `noEmit` and `skipLibCheck` default to `true` and `maxNodeModuleJsDepth` defaults to `2`. Options set in
+    /// the file, or in the files it extends, take precedence over these defaults. The returned configuration has
+    /// [TsConfig::is_jsconfig] set.
+    ///
+    /// ## Example
+    /// ```
+    /// use std::path::Path;
+    /// use tsconfig::TsConfig;
+    ///
+    /// let path = Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap())
+    ///     .join("test/jsconfig.json");
+    /// let config = TsConfig::parse_jsconfig_file(&path).unwrap();
+    ///
+    /// assert!(config.is_jsconfig);
+    /// assert_eq!(config.compiler_options.unwrap().allow_js, Some(true));
+    /// ```
+    pub fn parse_jsconfig_file<P: AsRef<Path>>(path: &P) -> Result<TsConfig> {
+        let mut values = parse_file_to_value(path)?;
+        merge(
+            &mut values,
+            serde_json::json!({
+                "compilerOptions": {
+                    "allowJs": true,
+                    "allowSyntheticDefaultImports": true,
+                    "maxNodeModuleJsDepth": 2,
+                    "noEmit": true,
+                    "skipLibCheck": true,
+                }
+            }),
+        );
+        let mut cfg: TsConfig = serde_json::from_value(values)?;
+        cfg.is_jsconfig = true;
+        Ok(cfg)
+    }
+
+    /// Parses a .tsconfig file into both a [TsConfig] and the merged [Value] it was built from.
+    ///
+    /// The file and its `extends` chain are only read once. The [Value] still holds options this crate does not
+    /// model, exactly as they were written.
+    ///
+    /// ## Example
+    /// ```
+    /// use std::path::Path;
+    /// use tsconfig::TsConfig;
+    ///
+    /// let path = Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap())
+    ///     .join("test/tsconfig.inherits.json");
+    /// let (config, value) = TsConfig::parse_file_with_value(&path).unwrap();
+    ///
+    /// assert_eq!(config.compiler_options.unwrap().trace_resolution, Some(false));
+    /// assert_eq!(value["compilerOptions"]["traceResolution"], false);
+    /// ```
+    pub fn parse_file_with_value<P: AsRef<Path>>(path: &P) -> Result<(TsConfig, Value)> {
+        let values = parse_file_to_value(path)?;
+        let cfg = TsConfig::deserialize(&values)?;
+        Ok((cfg, values))
+    }
+
+    /// Parses a .tsconfig file into a [TsConfig], using `resolver` to locate and read it and the files it extends.
+    ///
+    /// This behaves like [TsConfig::parse_file], but nothing is read from disk except through `resolver`, so
+    /// configurations can be loaded from a virtual filesystem. See [parse_file_to_value_with_resolver].
+    pub fn parse_with_resolver<P: AsRef<Path>, R: ConfigResolver + ?Sized>(
+        path: &P,
+        resolver: &R,
+    ) -> Result<TsConfig> {
+        let values = parse_file_to_value_with_resolver(path, resolver)?;
+        let cfg = serde_json::from_value(values)?;
+        Ok(cfg)
+    }
+
+    /// Checks every file in the `extends` chain of the file at `path` on its own, returning all the problems found.
+    ///
+    /// Where [TsConfig::parse_file] stops at the first error, this keeps going, so that several broken files can
+    /// be reported at once. Each error names its file, as in [TsConfig::parse_file], and each file is checked as a
+    /// configuration in its own right. A file that cannot be parsed at all hides the files it extends, which are
+    /// only checked once it is fixed. An empty list means the whole chain loaded successfully.
+    ///
+    /// ## Example
+    /// ```
+    /// use std::path::Path;
+    /// use tsconfig::TsConfig;
+    ///
+    /// let path = Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap())
+    ///     .join("test/tsconfig.inherits.json");
+    /// assert!(TsConfig::check_file(&path).is_empty());
+    /// ```
+    pub fn check_file<P: AsRef<Path>>(path: &P) -> Vec<ConfigError> {
+        let mut errors = Vec::new();
+        check_chain(path.as_ref(), &FsResolver, &mut Vec::new(), &mut errors);
+        errors
+    }
+
+    /// Parses a .tsconfig file into a [TsConfig], along with the file each compiler option was taken from.
+    ///
+    /// The map is keyed by the option's name as written in the file, such as `"strict"`, and points at the file
+    /// in the `extends` chain that supplied the value which won the merge.
+    ///
+    /// ## Example
+    /// ```
+    /// use std::path::Path;
+    /// use tsconfig::TsConfig;
+    ///
+    /// let path = Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap())
+    ///     .join("test/tsconfig.inherits.json");
+    /// let (config, provenance) = TsConfig::parse_file_with_provenance(&path).unwrap();
+    ///
+    /// assert_eq!(config.compiler_options.unwrap().trace_resolution, Some(false));
+    /// assert_eq!(provenance["traceResolution"], path);
+    /// assert!(provenance["useDefineForClassFields"].ends_with("tsconfig.base.json"));
+    /// ```
+    pub fn parse_file_with_provenance<P: AsRef<Path>>(
+        path: &P,
+    ) -> Result<(TsConfig, HashMap<String, PathBuf>)> {
+        let mut provenance = HashMap::new();
+        let values = load_file_to_value(
+            path.as_ref(),
+            &FsResolver,
+            &mut Vec::new(),
+            Some(&mut provenance),
+        )?;
+        let cfg = serde_json::from_value(values)?;
+        Ok((cfg, provenance))
+    }
+
+    /// Parses a .tsconfig file into a [TsConfig] without blocking, reading every file in the `extends` chain
+    /// through `tokio::fs`.
+    ///
+    /// Requires the `tokio` feature. Apart from how files are read, this behaves like [TsConfig::parse_file].
+    #[cfg(feature = "tokio")]
+    pub async fn parse_file_async<P: AsRef<Path>>(path: &P) -> Result<TsConfig> {
+        let values = parse_file_to_value_async(path).await?;
+        let cfg = serde_json::from_value(values)?;
+        Ok(cfg)
+    }
+
     /// Parse a JSON string into a single [TsConfig].
     ///
     /// The 'extends' field will be ignored. Comments and trailing commas are both allowed, although they are not valid JSON.
@@ -126,866 +455,6384 @@ impl TsConfig {
     ///```
     ///
     pub fn parse_str(json: &str) -> Result<TsConfig> {
-        // Remove trailing commas from objects.
-        let re = Regex::new(r",(?P<valid>\s*})").unwrap();
-        let mut stripped = String::with_capacity(json.len());
-        StripComments::new(json.as_bytes()).read_to_string(&mut stripped)?;
-        let stripped = re.replace_all(&stripped, "$valid");
-        let r: TsConfig = serde_json::from_str(&stripped)?;
+        let stripped = sanitize_json(json)?;
+        let r: TsConfig =
+            serde_json::from_str(&stripped).map_err(|e| json_error(json, &stripped, e))?;
         Ok(r)
     }
-}
 
-fn merge(a: &mut Value, b: Value) {
-    match (a, b) {
-        (&mut Value::Object(ref mut a), Value::Object(b)) => {
-            for (k, v) in b {
-                merge(a.entry(k).or_insert(Value::Null), v);
+    /// Parse a JSON string into a single [TsConfig], rejecting options this crate does not recognise.
+    ///
+    /// This behaves like [TsConfig::parse_str], except that unknown keys at the top level or under
+    /// `compilerOptions`, such as a misspelled option name, fail with [ConfigError::UnknownOption]. A key that
+    /// appears twice in the same object, which would otherwise silently take the last value, fails with
+    /// [ConfigError::DuplicateKey].
+    ///
+    /// ## Example
+    /// ```
+    /// use tsconfig::{ConfigError, TsConfig};
+    ///
+    /// let json = r#"{"compilerOptions": {"strict": true}}"#;
+    /// assert!(TsConfig::parse_str_strict(json).is_ok());
+    ///
+    /// let json = r#"{"compilerOptions": {"strcit": true}}"#;
+    /// match TsConfig::parse_str_strict(json) {
+    ///     Err(ConfigError::UnknownOption(option)) => assert_eq!(option, "compilerOptions.strcit"),
+    ///     other => panic!("expected an unknown option, got {:?}", other),
+    /// }
+    /// ```
+    pub fn parse_str_strict(json: &str) -> Result<TsConfig> {
+        let stripped = sanitize_json(json)?;
+        let value: Value =
+            serde_json::from_str(&stripped).map_err(|e| json_error(json, &stripped, e))?;
+        let mut scanner = Scanner {
+            json: stripped.as_bytes(),
+            pos: 0,
+        };
+        scanner.skip_whitespace();
+        if let Some(key) = scanner.duplicate_key("") {
+            return Err(ConfigError::DuplicateKey(key));
+        }
+        if let Some(key) = value.as_object().and_then(|object| {
+            object
+                .keys()
+                .find(|key| !TOP_LEVEL_KEYS.contains(&key.as_str()))
+        }) {
+            return Err(ConfigError::UnknownOption(key.clone()));
+        }
+
+        let config = TsConfig::deserialize(&value)?;
+        if let Some(options) = &config.compiler_options {
+            if let Some(key) = options.extra.keys().min() {
+                return Err(ConfigError::UnknownOption(format!(
+                    "compilerOptions.{}",
+                    key
+                )));
             }
         }
-        (a, b) => {
-            if let Value::Null = a {
-                *a = b;
+        Ok(config)
+    }
+
+    /// Lists the files this configuration selects when `project_dir` is the directory containing it.
+    ///
+    /// The explicit `files` come first, followed by every file matched by the `include` globs and not matched by
+    /// `exclude`, in directory order. `exclude` only filters `include`, so a file listed in `files` is kept even if
+    /// it matches `exclude`. An `include` entry whose last segment has neither a wildcard nor an extension, such
+    /// as `src`, names a directory and includes everything below it. `include` defaults to `**/*` unless `files` is
+    /// set, and the implicit `exclude` defaults are described in [TsConfig::effective_exclude]. As in TypeScript,
+    /// `*` and `?` match within a single path segment, `**/` matches any number of directories, and wildcards skip
+    /// names starting with a dot. Globbed files must have one of the [CompilerOptions::included_extensions], and
+    /// JSON files are only matched by an `include` pattern ending in `.json`.
+    ///
+    /// The `extends` field is not followed, so this should be called on a configuration loaded with
+    /// [TsConfig::parse_file].
+    ///
+    /// ## Example
+    /// ```
+    /// use std::path::Path;
+    /// use tsconfig::TsConfig;
+    ///
+    /// let project_dir = Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap()).join("test/project");
+    /// let config = TsConfig::parse_file(&project_dir.join("tsconfig.json")).unwrap();
+    ///
+    /// let files = config.included_files(&project_dir).unwrap();
+    /// assert!(files.contains(&project_dir.join("src/index.ts")));
+    /// assert!(!files.contains(&project_dir.join("node_modules/dep/index.ts")));
+    /// ```
+    pub fn included_files(&self, project_dir: &Path) -> Result<Vec<PathBuf>> {
+        let default_options = CompilerOptions::default();
+        let extensions = self
+            .compiler_options
+            .as_ref()
+            .unwrap_or(&default_options)
+            .included_extensions();
+
+        let include = match (&self.include, &self.files) {
+            (Some(include), _) => include.clone(),
+            (None, Some(_)) => Vec::new(),
+            (None, None) => vec!["**/*".to_string()],
+        };
+        let exclude: Vec<Vec<String>> = self
+            .effective_exclude()
+            .iter()
+            .map(|pattern| path_segments(project_dir, pattern))
+            .collect();
+
+        let mut found = FileList::default();
+        for file in self.files.iter().flatten() {
+            found.push(path_segments(project_dir, file).iter().collect());
+        }
+        for pattern in &include {
+            let mut pattern = path_segments(project_dir, pattern);
+            // An empty pattern is the project directory itself, such as `.` in a relative project directory.
+            if pattern
+                .last()
+                .map_or(true, |last| !has_wildcard(last) && !last.contains('.'))
+            {
+                pattern.extend(["**".to_string(), "*".to_string()]);
+            }
+            let base_len = pattern
+                .iter()
+                .take(pattern.len().saturating_sub(1))
+                .take_while(|segment| !has_wildcard(segment))
+                .count();
+            // As in TypeScript, JSON files are only picked up by patterns that name them explicitly.
+            let json = pattern.last().is_some_and(|last| last.ends_with(".json"));
+            let extensions: Vec<&str> = extensions
+                .iter()
+                .copied()
+                .filter(|ext| json || *ext != ".json")
+                .collect();
+            let mut segments = pattern[..base_len].to_vec();
+            let base: PathBuf = if segments.is_empty() {
+                PathBuf::from(".")
+            } else {
+                segments.iter().collect()
+            };
+            if base.is_dir() {
+                let mut walk = Walk {
+                    pattern: &pattern,
+                    exclude: &exclude,
+                    extensions: &extensions,
+                    visited: HashSet::new(),
+                };
+                walk.visit(&base, &mut segments, &mut found)?;
             }
         }
+        Ok(found.files)
     }
-}
 
-/// Parses a .tsconfig file into a [serde_json::Value].
-///
-/// The `extends` field will be respected, allowing for one .tsconfig file to inherit properties from another.
-/// Comments and trailing commas are both allowed, although they are not valid JSON.
-/// ## Example
-///
-/// Assuming the following .tsconfig files:
-///
-/// tsconfig.base.json:
-/// ```json
-/// {
-///     "compilerOptions": {
-///         "useDefineForClassFields": false,
-///         "traceResolution": true,
-///         "jsx": "preserve",
-///     }
-/// }
-/// ```
-/// tsconfig.inherits.json:
-/// ```json
-/// {
-///     "extends": "./tsconfig.base.json",
-///     "compilerOptions": {
-///         "traceResolution": false,
-///         "declaration": true,
-///         "jsx": "react-jsxdev",
-///     }
-/// }
-/// ```
-/// ```
-/// use std::path::Path;
-/// use tsconfig::parse_file_to_value;
-/// use serde_json::Value;
-///
-/// let path = Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap())
-///     .join("test/tsconfig.inherits.json");
-/// let config = parse_file_to_value(&path).unwrap();
-///
-/// assert_eq!(
-///     config
-///         ["compilerOptions"]
-///         ["useDefineForClassFields"],
-///     Value::Bool(false)
-/// );
-///
-///
-/// ```
-pub fn parse_file_to_value<P: AsRef<Path>>(path: &P) -> Result<Value> {
-    let s = std::fs::read_to_string(path)?;
-    let mut value = parse_to_value(&s)?;
+    /// The `exclude` patterns that apply to `include`, taking the implicit defaults into account.
+    ///
+    /// As in TypeScript, an `exclude` that is set is used as it is. Otherwise `exclude` defaults to
+    /// `node_modules`, `bower_components` and `jspm_packages`, followed by `outDir` and `declarationDir` when they
+    /// are set.
+    ///
+    /// ## Example
+    /// ```
+    /// use tsconfig::TsConfig;
+    ///
+    /// let json = r#"{"compilerOptions": {"outDir": "dist", "declarationDir": "types"}}"#;
+    /// let config = TsConfig::parse_str(json).unwrap();
+    /// assert_eq!(
+    ///     config.effective_exclude(),
+    ///     vec!["node_modules", "bower_components", "jspm_packages", "dist", "types"]
+    /// );
+    ///
+    /// let json = r#"{"exclude": ["tmp"], "compilerOptions": {"outDir": "dist"}}"#;
+    /// let config = TsConfig::parse_str(json).unwrap();
+    /// assert_eq!(config.effective_exclude(), vec!["tmp"]);
+    /// ```
+    pub fn effective_exclude(&self) -> Vec<String> {
+        if let Some(exclude) = &self.exclude {
+            return exclude.clone();
+        }
+        let mut exclude: Vec<String> = ["node_modules", "bower_components", "jspm_packages"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        if let Some(options) = &self.compiler_options {
+            exclude.extend(options.out_dir.iter().cloned());
+            exclude.extend(options.declaration_dir.iter().cloned());
+        }
+        exclude
+    }
 
-    if let Value::String(s) = &value["extends"] {
-        let extends_path = path
-            .as_ref()
-            .parent()
-            .unwrap_or_else(|| Path::new(""))
-            .join(s);
-        let extends_value = parse_file_to_value(&extends_path)?;
-        merge(&mut value, extends_value);
+    /// Reads a JSON document from `reader` and parses it into a single [TsConfig], like [TsConfig::parse_str].
+    ///
+    /// The reader is read to the end exactly once, so it need not be seekable, and it can be wrapped in a
+    /// decompressor or any other adapter. Fails with [ConfigError::CouldNotFindFile] if reading fails, and with
+    /// [ConfigError::InvalidUtf8] if the data is not UTF-8.
+    ///
+    /// ## Example
+    /// ```
+    /// use tsconfig::TsConfig;
+    ///
+    /// let data: &[u8] = br#"{"compilerOptions": {"strict": true, /* from a socket */}}"#;
+    /// let config = TsConfig::parse_reader(data).unwrap();
+    /// assert_eq!(config.compiler_options.unwrap().strict, Some(true));
+    /// ```
+    pub fn parse_reader<R: Read>(mut reader: R) -> Result<TsConfig> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        TsConfig::parse_bytes(&bytes)
     }
 
-    Ok(value)
-}
+    /// Parses a JSON document held in memory as bytes into a single [TsConfig], like [TsConfig::parse_str].
+    ///
+    /// Fails with [ConfigError::InvalidUtf8] if the data is not UTF-8.
+    pub fn parse_bytes(bytes: &[u8]) -> Result<TsConfig> {
+        TsConfig::parse_str(std::str::from_utf8(bytes)?)
+    }
 
-/// Parse a JSON string into a single [serde_json::Value].
-///
-/// The 'extends' field will be ignored. Comments and trailing commas are both allowed, although they are not valid JSON.
-///
-/// ## Example
-/// ```
-/// use tsconfig::parse_to_value;
-/// use serde_json::Value;
-///
-///
-/// let json = r#"{"compilerOptions": {"jsx": /*here's a comment*/ "react-jsx"},}"#;
-///
-/// let config = parse_to_value(json).unwrap();
-/// assert_eq!(config["compilerOptions"]["jsx"], Value::String("react-jsx".to_string()));     
-///```
-///
-pub fn parse_to_value(json: &str) -> Result<Value> {
-    // Remove trailing commas from objects.
-    let re = Regex::new(r",(?P<valid>\s*})").unwrap();
-    let mut stripped = String::with_capacity(json.len());
-    StripComments::new(json.as_bytes()).read_to_string(&mut stripped)?;
-    let stripped = re.replace_all(&stripped, "$valid");
-    let r: Value = serde_json::from_str(&stripped)?;
-    Ok(r)
-}
+    /// Checks the compiler options against the rules `tsc` enforces between them, returning every violation.
+    ///
+    /// An empty list means no problems were found. Only the options set in this configuration are considered,
+    /// so configurations loaded through [TsConfig::parse_file] are checked with everything they inherit.
+    ///
+    /// Combinations that tsc accepts are not reported, even where an option has no effect. `outDir` alongside
+    /// `outFile` is one of them, since tsc simply ignores `outDir`. `paths` without `baseUrl` is another, as it is
+    /// only rejected before TypeScript 4.1; call [CompilerOptions::path_mappings_require_base_url] to check for it
+    /// when targeting an older compiler.
+    ///
+    /// ## Example
+    /// ```
+    /// use tsconfig::{TsConfig, ValidationError};
+    ///
+    /// let json = r#"{"compilerOptions": {"composite": true, "declaration": false}}"#;
+    /// let errors = TsConfig::parse_str(json).unwrap().validate();
+    /// assert_eq!(errors, vec![ValidationError::CompositeWithoutDeclaration]);
+    /// ```
+    pub fn validate(&self) -> Vec<ValidationError> {
+        let options = match &self.compiler_options {
+            Some(options) => options,
+            None => return Vec::new(),
+        };
+        let mut errors = Vec::new();
 
-/// Project references setting  
-///
-/// Project references are a way to structure your TypeScript programs into smaller pieces. Using
-/// Project References can greatly improve build and editor interaction times, enforce logical separation
-/// between components, and organize your code in new and improved ways.
-///
-/// You can read more about how references works in the Project References section of [the handbook](https://www.typescriptlang.org/docs/handbook/project-references.html).
-#[derive(Deserialize, Debug, Clone)]
-#[serde(untagged)]
-pub enum References {
-    Bool(bool),
-    References(Vec<Reference>),
-}
+        let composite = options.composite == Some(true);
+        if composite && options.declaration == Some(false) {
+            errors.push(ValidationError::CompositeWithoutDeclaration);
+        }
+        if composite && options.incremental == Some(false) {
+            errors.push(ValidationError::CompositeWithoutIncremental);
+        }
 
-/// Project references setting  
-///
-/// Project references are a way to structure your TypeScript programs into smaller pieces. Using
-/// Project References can greatly improve build and editor interaction times, enforce logical separation
-/// between components, and organize your code in new and improved ways.
-///
-/// You can read more about how references works in the Project References section of [the handbook](https://www.typescriptlang.org/docs/handbook/project-references.html).
-#[derive(Deserialize, Debug, Clone)]
-pub struct Reference {
-    pub path: String,
-    pub prepend: Option<bool>,
+        let declaration = composite || options.declaration == Some(true);
+        if !declaration {
+            if options.emit_declaration_only == Some(true) {
+                errors.push(ValidationError::RequiresDeclaration("emitDeclarationOnly"));
+            }
+            if options.declaration_map == Some(true) {
+                errors.push(ValidationError::RequiresDeclaration("declarationMap"));
+            }
+        }
+
+        if options.no_emit == Some(true) && options.emit_declaration_only == Some(true) {
+            errors.push(ValidationError::ConflictingOptions(
+                "noEmit",
+                "emitDeclarationOnly",
+            ));
+        }
+
+        if options.out_file.is_some() && options.declaration_dir.is_some() {
+            errors.push(ValidationError::ConflictingOptions(
+                "declarationDir",
+                "outFile",
+            ));
+        }
+        if let (Some(_), Some(module)) = (&options.out_file, &options.module) {
+            if !matches!(module, Module::None | Module::Amd | Module::System) {
+                errors.push(ValidationError::OutFileWithModule(module.clone()));
+            }
+        }
+
+        errors
+    }
+
+    /// Lists the deprecated compiler options this configuration sets, naming the replacement where there is one.
+    ///
+    /// This covers the options marked `#[deprecated]` on [CompilerOptions] as well as those TypeScript 5.0
+    /// deprecated, such as `importsNotUsedAsValues`.
+    ///
+    /// ## Example
+    /// ```
+    /// use tsconfig::TsConfig;
+    ///
+    /// let json = r#"{"compilerOptions": {"out": "bundle.js", "charset": "utf8"}}"#;
+    /// let warnings = TsConfig::parse_str(json).unwrap().deprecation_warnings();
+    ///
+    /// assert_eq!(
+    ///     warnings,
+    ///     vec![
+    ///         "'charset' is deprecated and has no effect",
+    ///         "'out' is deprecated, use 'outFile' instead"
+    ///     ]
+    /// );
+    /// ```
+    #[allow(deprecated)]
+    pub fn deprecation_warnings(&self) -> Vec<String> {
+        let options = match &self.compiler_options {
+            Some(options) => options,
+            None => return Vec::new(),
+        };
+
+        let deprecated = [
+            ("charset", options.charset.is_some(), None),
+            (
+                "diagnostics",
+                options.diagnostics.is_some(),
+                Some("extendedDiagnostics"),
+            ),
+            ("out", options.out.is_some(), Some("outFile")),
+            (
+                "importsNotUsedAsValues",
+                options.imports_not_used_as_values.is_some(),
+                Some("verbatimModuleSyntax"),
+            ),
+            (
+                "preserveValueImports",
+                options.extra.contains_key("preserveValueImports"),
+                Some("verbatimModuleSyntax"),
+            ),
+            (
+                "keyofStringsOnly",
+                options.keyof_strings_only.is_some(),
+                None,
+            ),
+            (
+                "noImplicitUseStrict",
+                options.no_implicit_use_strict.is_some(),
+                None,
+            ),
+            (
+                "noStrictGenericChecks",
+                options.no_strict_generic_checks.is_some(),
+                None,
+            ),
+            (
+                "suppressExcessPropertyErrors",
+                options.suppress_excess_property_errors.is_some(),
+                None,
+            ),
+            (
+                "suppressImplicitAnyIndexErrors",
+                options.suppress_implicit_any_index_errors.is_some(),
+                None,
+            ),
+        ];
+
+        deprecated
+            .iter()
+            .filter(|(_, used, _)| *used)
+            .map(|(name, _, replacement)| match replacement {
+                Some(replacement) => {
+                    format!("'{}' is deprecated, use '{}' instead", name, replacement)
+                }
+                None => format!("'{}' is deprecated and has no effect", name),
+            })
+            .collect()
+    }
+
+    /// Borrows the compiler options, if the configuration sets any.
+    ///
+    /// ## Example
+    /// ```
+    /// use tsconfig::TsConfig;
+    ///
+    /// let config = TsConfig::parse_str(r#"{"compilerOptions": {"strict": true}}"#).unwrap();
+    /// assert_eq!(config.compiler_options().and_then(|options| options.strict), Some(true));
+    /// ```
+    pub fn compiler_options(&self) -> Option<&CompilerOptions> {
+        self.compiler_options.as_ref()
+    }
+
+    /// Whether the configuration deliberately selects no files, by setting `files` to an empty list without `include`.
+    ///
+    /// Leaving out `files` is different: with no `include` either, every file under the project directory is
+    /// included by default.
+    pub fn selects_no_files(&self) -> bool {
+        self.files.as_ref().is_some_and(Vec::is_empty)
+            && self.include.as_ref().map_or(true, Vec::is_empty)
+    }
+
+    /// Whether building this configuration writes any files.
+    ///
+    /// Nothing is written when `noEmit` is set, or when `files` and `include` are given but list no files. With
+    /// `emitDeclarationOnly`, only declaration files are written, so there is output only if `declaration` or
+    /// `composite` is also set. The `extends` field is not followed, so this should be called on a configuration
+    /// loaded with [TsConfig::parse_file].
+    ///
+    /// ## Example
+    /// ```
+    /// use tsconfig::TsConfig;
+    ///
+    /// let json = r#"{"compilerOptions": {"outDir": "dist"}}"#;
+    /// assert!(TsConfig::parse_str(json).unwrap().emits_output());
+    ///
+    /// let json = r#"{"compilerOptions": {"noEmit": true}}"#;
+    /// assert!(!TsConfig::parse_str(json).unwrap().emits_output());
+    ///
+    /// let json = r#"{"compilerOptions": {"emitDeclarationOnly": true, "declaration": true}}"#;
+    /// assert!(TsConfig::parse_str(json).unwrap().emits_output());
+    /// ```
+    pub fn emits_output(&self) -> bool {
+        let is_empty = |list: &Option<Vec<String>>| list.as_ref().map_or(true, Vec::is_empty);
+        let selects_nothing = (self.files.is_some() || self.include.is_some())
+            && is_empty(&self.files)
+            && is_empty(&self.include);
+        if selects_nothing {
+            return false;
+        }
+
+        let options = match &self.compiler_options {
+            Some(options) => options,
+            None => return true,
+        };
+        if options.no_emit == Some(true) {
+            return false;
+        }
+        options.emit_declaration_only != Some(true)
+            || options.declaration == Some(true)
+            || options.composite == Some(true)
+    }
+
+    /// Whether this is a "solution style" configuration, which only gathers other projects through `references`.
+    ///
+    /// That is the case when `references` lists at least one project while `files` and `include` are both absent
+    /// or empty, so the configuration has no files of its own to compile.
+    ///
+    /// ## Example
+    /// ```
+    /// use tsconfig::TsConfig;
+    ///
+    /// let json = r#"{"files": [], "references": [{"path": "./packages/core"}]}"#;
+    /// assert!(TsConfig::parse_str(json).unwrap().is_solution());
+    ///
+    /// let json = r#"{"include": ["src"], "references": [{"path": "./packages/core"}]}"#;
+    /// assert!(!TsConfig::parse_str(json).unwrap().is_solution());
+    /// ```
+    pub fn is_solution(&self) -> bool {
+        let has_references = matches!(
+            &self.references,
+            Some(References::References(references)) if !references.is_empty()
+        );
+        let is_empty = |list: &Option<Vec<String>>| list.as_ref().map_or(true, Vec::is_empty);
+        has_references && is_empty(&self.files) && is_empty(&self.include)
+    }
+
+    /// Loads the projects listed in `references`, where `config_dir` is the directory containing this configuration.
+    ///
+    /// As in TypeScript, a reference whose path ends in `.json` names a configuration file directly, and any other
+    /// path names a directory holding a `tsconfig.json`. Each project is parsed with [TsConfig::parse_file], so its
+    /// `extends` and `${configDir}` are resolved relative to its own location. Reference paths may climb out of
+    /// `config_dir` with `..`. Returns the canonical path of each configuration file along with the parsed
+    /// configuration, in the order they are listed, so the same project is always named by the same path.
+    ///
+    /// ## Example
+    /// ```
+    /// use std::path::Path;
+    /// use tsconfig::TsConfig;
+    ///
+    /// let app_dir = Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap()).join("test/refs/app");
+    /// let config = TsConfig::parse_file(&app_dir.join("tsconfig.json")).unwrap();
+    ///
+    /// let references = config.load_references(&app_dir).unwrap();
+    /// assert_eq!(references.len(), 2);
+    /// assert!(references[0].0.ends_with("refs/core/tsconfig.json"));
+    /// ```
+    pub fn load_references(&self, config_dir: &Path) -> Result<Vec<(PathBuf, TsConfig)>> {
+        let references = match &self.references {
+            Some(References::References(references)) => references,
+            _ => return Ok(Vec::new()),
+        };
+
+        references
+            .iter()
+            .map(|reference| {
+                let path = reference_config_path(config_dir, &reference.path);
+                let config = TsConfig::parse_file(&path)?;
+                Ok((FsResolver.canonicalize(&path)?, config))
+            })
+            .collect()
+    }
+
+    /// Lists the projects this configuration references, directly or indirectly, in the order they must be built.
+    ///
+    /// Every project comes after the projects it references, and each appears once even if it is referenced from
+    /// several places. The configuration itself, which would be built last, is not included. Fails with
+    /// [ConfigError::CircularReferences] if the references form a cycle.
+    ///
+    /// ## Example
+    /// ```
+    /// use std::path::Path;
+    /// use tsconfig::TsConfig;
+    ///
+    /// let refs_dir = Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap()).join("test/refs");
+    /// let config = TsConfig::parse_file(&refs_dir.join("app/tsconfig.json")).unwrap();
+    ///
+    /// let order = config.build_order(&refs_dir.join("app")).unwrap();
+    /// assert_eq!(
+    ///     order,
+    ///     vec![refs_dir.join("core/tsconfig.json"), refs_dir.join("utils/tsconfig.build.json")]
+    /// );
+    /// ```
+    pub fn build_order(&self, config_dir: &Path) -> Result<Vec<PathBuf>> {
+        let mut order = BuildOrder::default();
+        for (path, config) in self.load_references(config_dir)? {
+            order.visit(path, &config)?;
+        }
+        Ok(order.projects)
+    }
+
+    /// Layers this configuration over `base`, as if this configuration's file extended the base's.
+    ///
+    /// Fields set here win, options under `compilerOptions`, `watchOptions` and `typeAcquisition` are inherited
+    /// one by one, and any other field, such as `include` or `paths`, is only taken from the base when it is not set
+    /// here at all. Compiler options this crate does not model are merged by name. `references` and `extends` are
+    /// never inherited.
+    ///
+    /// ## Example
+    /// ```
+    /// use tsconfig::{Target, TsConfig};
+    ///
+    /// let defaults = TsConfig::builder().target(Target::Es2020).strict(true).build();
+    /// let overrides = TsConfig::builder().strict(false).build();
+    /// let options = overrides.merged_with(defaults).compiler_options.unwrap();
+    ///
+    /// assert_eq!(options.target, Some(Target::Es2020));
+    /// assert_eq!(options.strict, Some(false));
+    /// ```
+    pub fn merged_with(self, base: TsConfig) -> TsConfig {
+        TsConfig {
+            schema: self.schema.or(base.schema),
+            compile_on_save: self.compile_on_save.or(base.compile_on_save),
+            exclude: self.exclude.or(base.exclude),
+            extends: self.extends,
+            files: self.files.or(base.files),
+            include: self.include.or(base.include),
+            references: self.references,
+            type_acquisition: merge_options(
+                self.type_acquisition,
+                base.type_acquisition,
+                TypeAcquisition::merged_with,
+            ),
+            types_versions: self.types_versions.or(base.types_versions),
+            watch_options: merge_options(
+                self.watch_options,
+                base.watch_options,
+                WatchOptions::merged_with,
+            ),
+            compiler_options: merge_options(
+                self.compiler_options,
+                base.compiler_options,
+                CompilerOptions::merged_with,
+            ),
+            is_jsconfig: self.is_jsconfig,
+        }
+    }
+
+    /// Returns a [TsConfigBuilder] for constructing a configuration programmatically.
+    pub fn builder() -> TsConfigBuilder {
+        TsConfigBuilder::new()
+    }
 }
 
-/// Defines how automatic type acquisition behaves.
+/// The keys [TsConfig::parse_str_strict] accepts at the top level of a file.
+const TOP_LEVEL_KEYS: &[&str] = &[
+    "$schema",
+    "compileOnSave",
+    "compilerOptions",
+    "exclude",
+    "extends",
+    "files",
+    "include",
+    "references",
+    "typeAcquisition",
+    "typesVersions",
+    "watchOptions",
+];
+
+/// A depth-first walk over project references, collecting projects after everything they reference.
+#[derive(Default)]
+struct BuildOrder {
+    projects: Vec<PathBuf>,
+    done: HashSet<PathBuf>,
+    visiting: Vec<PathBuf>,
+}
+
+impl BuildOrder {
+    fn visit(&mut self, path: PathBuf, config: &TsConfig) -> Result<()> {
+        if self.done.contains(&path) {
+            return Ok(());
+        }
+        if let Some(start) = self.visiting.iter().position(|p| p == &path) {
+            let mut cycle = self.visiting[start..].to_vec();
+            cycle.push(path);
+            return Err(ConfigError::CircularReferences(cycle));
+        }
+
+        let config_dir = path.parent().unwrap_or_else(|| Path::new(""));
+        let references = config.load_references(config_dir)?;
+        self.visiting.push(path);
+        for (reference, reference_config) in references {
+            self.visit(reference, &reference_config)?;
+        }
+        let path = self.visiting.pop().expect("the project being visited");
+
+        self.done.insert(path.clone());
+        self.projects.push(path);
+        Ok(())
+    }
+}
+
+/// The configuration file a project reference points at.
+fn reference_config_path(config_dir: &Path, reference: &str) -> PathBuf {
+    let path: PathBuf = path_segments(config_dir, reference).iter().collect();
+    if reference.ends_with(".json") {
+        path
+    } else {
+        path.join("tsconfig.json")
+    }
+}
+
+/// Files collected by [TsConfig::included_files], in the order they were found and without duplicates.
+#[derive(Default)]
+struct FileList {
+    files: Vec<PathBuf>,
+    seen: HashSet<PathBuf>,
+}
+
+impl FileList {
+    fn push(&mut self, file: PathBuf) {
+        if self.seen.insert(file.clone()) {
+            self.files.push(file);
+        }
+    }
+}
+
+/// A directory walk matching files against one `include` pattern.
+struct Walk<'a> {
+    pattern: &'a [String],
+    exclude: &'a [Vec<String>],
+    extensions: &'a [&'a str],
+    /// The canonical paths of the directories walked so far, so that symbolic links back into them are not
+    /// followed round in a loop.
+    visited: HashSet<PathBuf>,
+}
+
+impl Walk<'_> {
+    fn visit(
+        &mut self,
+        dir: &Path,
+        segments: &mut Vec<String>,
+        found: &mut FileList,
+    ) -> Result<()> {
+        // As in TypeScript, a directory reached a second time through a link is skipped.
+        if !self.visited.insert(std::fs::canonicalize(dir)?) {
+            return Ok(());
+        }
+
+        let mut entries = std::fs::read_dir(dir)?.collect::<std::io::Result<Vec<_>>>()?;
+        entries.sort_by_key(|entry| entry.file_name());
+
+        for entry in entries {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            segments.push(name);
+            let path = entry.path();
+            let excluded = self
+                .exclude
+                .iter()
+                .any(|pattern| glob_matches(pattern, segments));
+
+            if excluded {
+                // An excluded directory excludes everything inside it.
+            } else if path.is_dir() {
+                if self.pattern.len() > segments.len() || self.pattern.iter().any(|s| s == "**") {
+                    self.visit(&path, segments, found)?;
+                }
+            } else if self
+                .extensions
+                .iter()
+                .any(|ext| segments[segments.len() - 1].ends_with(ext))
+                && glob_matches(self.pattern, segments)
+            {
+                // Built from the segments rather than the walked path, so no `./` is added in front of files
+                // found from a relative project directory.
+                found.push(segments.iter().collect());
+            }
+            segments.pop();
+        }
+        Ok(())
+    }
+}
+
+/// Splits `pattern`, taken relative to `project_dir`, into lexically normalised path segments.
 ///
-/// When you have a JavaScript project in your editor, TypeScript will provide types for your node_modules automatically
-/// using the DefinitelyTyped set of @types definitions. This is called automatic type acquisition, and you can customize
-/// it using the typeAcquisition object in your configuration.
+/// Used for glob patterns as well as plain paths, so wildcards are kept as they are.
+fn path_segments(project_dir: &Path, pattern: &str) -> Vec<String> {
+    let mut segments: Vec<String> = Vec::new();
+    for component in project_dir.join(pattern).components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir
+                if segments
+                    .last()
+                    .is_some_and(|last| last != ".." && !Path::new(last).has_root()) =>
+            {
+                segments.pop();
+            }
+            component => segments.push(component.as_os_str().to_string_lossy().into_owned()),
+        }
+    }
+    segments
+}
+
+fn has_wildcard(segment: &str) -> bool {
+    segment.contains('*') || segment.contains('?')
+}
+
+/// Matches path segments against glob segments, where `**` stands for any number of directories.
+fn glob_matches(pattern: &[String], path: &[String]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((segment, rest)) if segment == "**" => {
+            for skipped in 0..=path.len() {
+                if glob_matches(rest, &path[skipped..]) {
+                    return true;
+                }
+                if path.get(skipped).map_or(true, |name| name.starts_with('.')) {
+                    return false;
+                }
+            }
+            false
+        }
+        Some((segment, rest)) => match path.split_first() {
+            Some((name, path)) => segment_matches(segment, name) && glob_matches(rest, path),
+            None => false,
+        },
+    }
+}
+
+/// Matches a single file or directory name against a pattern containing `*` and `?` wildcards.
+fn segment_matches(pattern: &str, name: &str) -> bool {
+    if !has_wildcard(pattern) {
+        return pattern == name;
+    }
+    if name.starts_with('.') && !pattern.starts_with('.') {
+        return false;
+    }
+
+    fn matches(pattern: &[char], name: &[char]) -> bool {
+        match pattern.split_first() {
+            None => name.is_empty(),
+            Some(('*', rest)) => (0..=name.len()).any(|skipped| matches(rest, &name[skipped..])),
+            Some(('?', rest)) => !name.is_empty() && matches(rest, &name[1..]),
+            Some((c, rest)) => name.first() == Some(c) && matches(rest, &name[1..]),
+        }
+    }
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    matches(&pattern, &name)
+}
+
+/// Builds a [TsConfig] from scratch, for example to emit a fresh tsconfig.json.
 ///
-/// If you would like to disable or customize this feature, create a jsconfig.json in the root of your project:
+/// Options without a dedicated setter can be passed as a whole through [TsConfigBuilder::compiler_options].
+/// Each setter overwrites whatever an earlier call set for the same key; lists are replaced, not extended.
 ///
-/// ```json
-/// {
-///   "typeAcquisition": {
-///     "enable": false
-///   }
-/// }
+/// ## Example
 /// ```
+/// use tsconfig::{Target, TsConfigBuilder};
 ///
-/// If you have a specific module which should be included (but isn’t in node_modules):
+/// let config = TsConfigBuilder::new()
+///     .target(Target::Es2020)
+///     .strict(true)
+///     .include(vec!["src".to_string()])
+///     .build();
 ///
-/// ```json
-/// {
-///   "typeAcquisition": {
-///     "include": ["jest"]
-///   }
-/// }
+/// let json = serde_json::to_string(&config).unwrap();
+/// assert_eq!(
+///     json,
+///     r#"{"include":["src"],"compilerOptions":{"target":"es2020","strict":true}}"#
+/// );
 /// ```
+#[derive(Debug, Clone, Default)]
+pub struct TsConfigBuilder {
+    config: TsConfig,
+}
+
+impl TsConfigBuilder {
+    /// Creates a builder for an empty configuration.
+    pub fn new() -> TsConfigBuilder {
+        TsConfigBuilder::default()
+    }
+
+    /// Sets `extends`.
+    pub fn extends(mut self, extends: Extends) -> TsConfigBuilder {
+        self.config.extends = Some(extends);
+        self
+    }
+
+    /// Sets `files`, replacing any list set before.
+    pub fn files(mut self, files: Vec<String>) -> TsConfigBuilder {
+        self.config.files = Some(files);
+        self
+    }
+
+    /// Sets `include`, replacing any list set before.
+    pub fn include(mut self, include: Vec<String>) -> TsConfigBuilder {
+        self.config.include = Some(include);
+        self
+    }
+
+    /// Sets `exclude`, replacing any list set before.
+    pub fn exclude(mut self, exclude: Vec<String>) -> TsConfigBuilder {
+        self.config.exclude = Some(exclude);
+        self
+    }
+
+    /// Sets `references`.
+    pub fn references(mut self, references: References) -> TsConfigBuilder {
+        self.config.references = Some(references);
+        self
+    }
+
+    /// Sets `compilerOptions` as a whole, replacing any options set before, including through the other setters.
+    pub fn compiler_options(mut self, options: CompilerOptions) -> TsConfigBuilder {
+        self.config.compiler_options = Some(options);
+        self
+    }
+
+    /// Sets `compilerOptions.target`.
+    pub fn target(mut self, target: Target) -> TsConfigBuilder {
+        self.options().target = Some(target);
+        self
+    }
+
+    /// Sets `compilerOptions.module`.
+    pub fn module(mut self, module: Module) -> TsConfigBuilder {
+        self.options().module = Some(module);
+        self
+    }
+
+    /// Sets `compilerOptions.moduleResolution`.
+    pub fn module_resolution(mut self, mode: ModuleResolutionMode) -> TsConfigBuilder {
+        self.options().module_resolution = Some(mode);
+        self
+    }
+
+    /// Sets `compilerOptions.strict`.
+    pub fn strict(mut self, strict: bool) -> TsConfigBuilder {
+        self.options().strict = Some(strict);
+        self
+    }
+
+    /// Sets `compilerOptions.jsx`.
+    pub fn jsx(mut self, jsx: Jsx) -> TsConfigBuilder {
+        self.options().jsx = Some(jsx);
+        self
+    }
+
+    /// Sets `compilerOptions.outDir`.
+    pub fn out_dir(mut self, out_dir: &str) -> TsConfigBuilder {
+        self.options().out_dir = Some(out_dir.to_string());
+        self
+    }
+
+    /// Returns the configuration built so far.
+    pub fn build(self) -> TsConfig {
+        self.config
+    }
+
+    fn options(&mut self) -> &mut CompilerOptions {
+        self.config
+            .compiler_options
+            .get_or_insert_with(CompilerOptions::default)
+    }
+}
+
+/// Fills in the fields of the configuration `a` that it inherits from its base `b`, following TypeScript.
 ///
-/// If a module should not be automatically acquired, for example if the library is available in your node_modules but your team has agreed to not use it:
+/// Values already set in `a` win. The option objects listed in [MERGED_OBJECTS] are inherited option by option,
+/// while every other field, including arrays and object-valued options such as `paths`, is taken from the base
+/// only when `a` does not set it at all. `references` is never inherited.
+fn merge(a: &mut Value, b: Value) {
+    let (a, b) = match (a, b) {
+        (Value::Object(a), Value::Object(b)) => (a, b),
+        _ => return,
+    };
+    for (key, base) in b {
+        if key == "references" {
+            continue;
+        }
+        let merge_options = MERGED_OBJECTS.contains(&key.as_str());
+        let value = a.entry(key).or_insert(Value::Null);
+        match (value, base) {
+            (Value::Object(options), Value::Object(base_options)) if merge_options => {
+                for (option, base) in base_options {
+                    let value = options.entry(option).or_insert(Value::Null);
+                    if value.is_null() {
+                        *value = base;
+                    }
+                }
+            }
+            (value, base) => {
+                if value.is_null() {
+                    *value = base;
+                }
+            }
+        }
+    }
+}
+
+/// Merges an object whose options are inherited one by one, for [TsConfig::merged_with].
+fn merge_options<T>(options: Option<T>, base: Option<T>, merged_with: fn(T, T) -> T) -> Option<T> {
+    match (options, base) {
+        (Some(options), Some(base)) => Some(merged_with(options, base)),
+        (options, base) => options.or(base),
+    }
+}
+
+/// Top-level objects whose individual options are inherited through `extends`.
+const MERGED_OBJECTS: [&str; 3] = ["compilerOptions", "watchOptions", "typeAcquisition"];
+
+/// Parses a .tsconfig file into a [serde_json::Value].
+///
+/// The `extends` field will be respected, allowing for one .tsconfig file to inherit properties from another.
+/// Comments and trailing commas are both allowed, although they are not valid JSON.
+/// Paths starting with `${configDir}` are expanded to the directory containing `path`.
+/// ## Example
+///
+/// Assuming the following .tsconfig files:
 ///
+/// tsconfig.base.json:
 /// ```json
 /// {
-///   "typeAcquisition": {
-///     "exclude": ["jquery"]
-///   }
+///     "compilerOptions": {
+///         "useDefineForClassFields": false,
+///         "traceResolution": true,
+///         "jsx": "preserve",
+///     }
 /// }
 /// ```
-///
-/// In TypeScript 4.1, we added the ability to disable the special-casing where a filename would trigger type acquisition:
-///
+/// tsconfig.inherits.json:
 /// ```json
 /// {
-///   "typeAcquisition": {
-///     "disableFilenameBasedTypeAcquisition": true
-///   }
+///     "extends": "./tsconfig.base.json",
+///     "compilerOptions": {
+///         "traceResolution": false,
+///         "declaration": true,
+///         "jsx": "react-jsxdev",
+///     }
 /// }
 /// ```
-///
-/// This means that having a file like jquery.js in your project would not automatically download the types for JQuery from DefinitelyTyped.
-///
-#[derive(Deserialize, Debug, Clone)]
-pub enum TypeAcquisition {
-    Bool(bool),
-    Object {
-        enable: bool,
-        include: Option<Vec<String>>,
-        exclude: Option<Vec<String>>,
-        disable_filename_based_type_acquisition: Option<bool>,
-    },
-}
-
-/// These options make up the bulk of TypeScript’s configuration and it covers how the language should work.
-#[derive(Deserialize, Debug, Clone)]
-#[serde(rename_all = "camelCase")]
-pub struct CompilerOptions {
-    pub allow_js: Option<bool>,
-    pub check_js: Option<bool>,
-    pub composite: Option<bool>,
-    pub declaration: Option<bool>,
-    pub declaration_map: Option<bool>,
-    pub downlevel_iteration: Option<bool>,
-    pub import_helpers: Option<bool>,
-    pub incremental: Option<bool>,
-    pub isolated_modules: Option<bool>,
-    pub jsx: Option<Jsx>,
-    pub lib: Option<Vec<Lib>>,
-    pub module: Option<Module>,
-    pub no_emit: Option<bool>,
-    pub out_dir: Option<String>,
-    pub out_file: Option<String>,
-    pub remove_comments: Option<bool>,
-    pub root_dir: Option<String>,
-    pub source_map: Option<bool>,
-    pub target: Option<Target>,
-    pub ts_build_info_file: Option<String>,
-    pub always_strict: Option<bool>,
-    pub no_implicit_any: Option<bool>,
-    pub no_implicit_this: Option<bool>,
-    pub strict: Option<bool>,
-    pub strict_bind_call_apply: Option<bool>,
-    pub strict_function_types: Option<bool>,
-    pub strict_null_checks: Option<bool>,
-    pub strict_property_initialization: Option<bool>,
-    pub allow_synthetic_default_imports: Option<bool>,
-    pub allow_umd_global_access: Option<bool>,
-    pub base_url: Option<String>,
-    pub es_module_interop: Option<bool>,
-    pub module_resolution: Option<ModuleResolutionMode>,
-    pub paths: Option<HashMap<String, Vec<String>>>,
-    pub preserve_symlinks: Option<bool>,
-    pub root_dirs: Option<Vec<String>>,
-    pub type_roots: Option<Vec<String>>,
-    pub types: Option<Vec<String>>,
-    pub inline_source_map: Option<bool>,
-    pub inline_sources: Option<bool>,
-    pub map_root: Option<String>,
-    pub source_root: Option<String>,
-    pub no_fallthrough_cases_in_switch: Option<bool>,
-    pub no_implicit_returns: Option<bool>,
-    pub no_property_access_from_index_signature: Option<bool>,
-    pub no_unchecked_indexed_access: Option<bool>,
-    pub no_unused_locals: Option<bool>,
-    pub emit_decorator_metadata: Option<bool>,
-    pub experimental_decorators: Option<bool>,
-    pub allow_unreachable_code: Option<bool>,
-    pub allow_unused_labels: Option<bool>,
-    pub assume_changes_only_affect_direct_dependencies: Option<bool>,
-    #[deprecated]
-    pub charset: Option<String>,
-    pub declaration_dir: Option<String>,
-    #[deprecated]
-    pub diagnostics: Option<bool>,
-    pub disable_referenced_project_load: Option<bool>,
-    pub disable_size_limit: Option<bool>,
-    pub disable_solution_searching: Option<bool>,
-    pub disable_source_of_project_reference_redirect: Option<bool>,
-    #[serde(rename = "emitBOM")]
-    pub emit_bom: Option<bool>,
-    pub emit_declaration_only: Option<bool>,
-    pub explain_files: Option<bool>,
-    pub extended_diagnostics: Option<bool>,
-    pub force_consistent_casing_in_file_names: Option<bool>,
-    // XXX: Is generateCpuProfile available from tsconfig? Or just the CLI?
-    pub generate_cpu_profile: Option<bool>,
-
-    pub imports_not_used_as_values: Option<String>,
-    pub jsx_factory: Option<String>,
-    pub jsx_fragment_factory: Option<String>,
-    pub jsx_import_source: Option<String>,
-
-    pub keyof_strings_only: Option<bool>,
-    pub list_emitted_files: Option<bool>,
-    pub list_files: Option<bool>,
-    pub max_node_module_js_depth: Option<u32>,
-    pub no_emit_helpers: Option<bool>,
-    pub no_emit_on_error: Option<bool>,
-    pub no_error_truncation: Option<bool>,
-    pub no_implicit_use_strict: Option<bool>,
-    pub no_lib: Option<bool>,
-    pub no_resolve: Option<bool>,
-    pub no_strict_generic_checks: Option<bool>,
-    #[deprecated]
-    pub out: Option<String>,
-    pub preserve_const_enums: Option<bool>,
-    pub react_namespace: Option<String>,
-    pub resolve_json_module: Option<bool>,
-    pub skip_default_lib_check: Option<bool>,
-    pub skip_lib_check: Option<bool>,
-    pub strip_internal: Option<bool>,
-    pub suppress_excess_property_errors: Option<bool>,
-    pub suppress_implicit_any_index_errors: Option<bool>,
-    pub trace_resolution: Option<bool>,
-    pub use_define_for_class_fields: Option<bool>,
-    pub preserve_watch_output: Option<bool>,
-    pub pretty: Option<bool>,
-    pub fallback_polling: Option<String>,
-    pub watch_directory: Option<String>,
-    pub watch_file: Option<String>,
-}
-
-/// Module resolution mode
-///
-/// Specify the module resolution strategy: 'node' (Node.js) or 'classic' (used in TypeScript before the release of 1.6). You probably won’t need to use classic in modern code.
-/// There is a handbook reference page [on Module Resolution](https://www.typescriptlang.org/docs/handbook/module-resolution.html).
-#[derive(Deserialize, Debug, PartialEq, Copy, Clone)]
-pub enum ModuleResolutionMode {
-    #[serde(rename = "node")]
-    Node,
-    #[serde(rename = "classic")]
-    Classic,
-}
-
-/// Controls how JSX constructs are emitted in JavaScript files. This only affects output of JS files that started in .tsx files.
-///
-///
-/// For example, this sample code:
-///
-/// ```tsx
-/// export const helloWorld = () => <h1>Hello world</h1>;
 /// ```
+/// use std::path::Path;
+/// use tsconfig::parse_file_to_value;
+/// use serde_json::Value;
 ///
-/// Default: "react"
+/// let path = Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap())
+///     .join("test/tsconfig.inherits.json");
+/// let config = parse_file_to_value(&path).unwrap();
 ///
-/// ```tsx
-/// export const helloWorld = () => React.createElement("h1", null, "Hello world");
-/// ```
+/// assert_eq!(
+///     config
+///         ["compilerOptions"]
+///         ["useDefineForClassFields"],
+///     Value::Bool(false)
+/// );
 ///
-/// Preserve: "preserve"
 ///
-/// ```tsx
-/// export const helloWorld = () => <h1>Hello world</h1>;
 /// ```
+pub fn parse_file_to_value<P: AsRef<Path>>(path: &P) -> Result<Value> {
+    parse_file_to_value_with_resolver(path, &FsResolver)
+}
+
+/// Parses a .tsconfig file into a [serde_json::Value] without blocking, reading every file in the `extends`
+/// chain through `tokio::fs`.
 ///
-/// React Native: "react-native"
+/// Requires the `tokio` feature. Apart from how files are read, this behaves like [parse_file_to_value].
+#[cfg(feature = "tokio")]
+pub async fn parse_file_to_value_async<P: AsRef<Path>>(path: &P) -> Result<Value> {
+    load_file_to_value_async(path.as_ref().to_path_buf(), &mut Vec::new()).await
+}
+
+/// Parses a .tsconfig file into a [serde_json::Value], using `resolver` to locate the files named in `extends`.
 ///
-/// ```tsx
-/// export const helloWorld = () => <h1>Hello world</h1>;
+/// This behaves like [parse_file_to_value], but allows the lookup of inherited configuration files to be
+/// overridden, for example to point package specifiers at fixtures in tests.
+///
+/// ## Example
 /// ```
+/// use std::path::{Path, PathBuf};
+/// use tsconfig::{parse_file_to_value_with_resolver, ConfigResolver, Result};
+/// use serde_json::Value;
 ///
-/// React 17 transform: "react-jsx"
+/// struct BaseResolver;
 ///
-/// ```tsx
-/// import { jsx as _jsx } from "react/jsx-runtime";
-/// export const helloWorld = () => _jsx("h1", { children: "Hello world" }, void 0);
-/// ```
+/// impl ConfigResolver for BaseResolver {
+///     fn resolve_extends(&self, config_path: &Path, _specifier: &str) -> Result<PathBuf> {
+///         Ok(config_path.parent().unwrap().join("b/tsconfig.base.json"))
+///     }
+/// }
 ///
-/// React 17 dev transform: "react-jsxdev"
+/// let path = Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap())
+///     .join("test/tsconfig.inherits.json");
+/// let config = parse_file_to_value_with_resolver(&path, &BaseResolver).unwrap();
 ///
-/// ```tsx
-/// import { jsxDEV as _jsxDEV } from "react/jsx-dev-runtime";
-/// const _jsxFileName = "/home/runner/work/TypeScript-Website/TypeScript-Website/packages/typescriptlang-org/index.tsx";
-/// export const helloWorld = () => _jsxDEV("h1", { children: "Hello world" }, void 0, false, { fileName: _jsxFileName, lineNumber: 7, columnNumber: 32 }, this);
+/// assert_eq!(config["compilerOptions"]["useDefineForClassFields"], Value::Bool(false));
 /// ```
-#[derive(Deserialize, Debug, PartialEq, Copy, Clone)]
-#[serde(rename_all = "kebab-case")]
-pub enum Jsx {
-    /// Emit .js files with JSX changed to the equivalent React.createElement calls
-    React,
-    /// Emit .js files with the JSX changed to _jsx calls
-    ReactJsx,
-    /// Emit .js files with the JSX to _jsx calls
-    ReactJsxdev,
-    /// Emit .js files with the JSX unchanged
-    ReactNative,
-    /// Emit .jsx files with the JSX unchanged
-    Preserve,
+pub fn parse_file_to_value_with_resolver<P: AsRef<Path>, R: ConfigResolver + ?Sized>(
+    path: &P,
+    resolver: &R,
+) -> Result<Value> {
+    load_file_to_value(path.as_ref(), resolver, &mut Vec::new(), None)
 }
 
-/// The transpilation target for the emitted JavaScript.
-///
-/// Modern browsers support all `ES6` features, so `ES6` is a good choice. You might choose to set a lower target if your code
-/// is deployed to older environments, or a higher target if your code is guaranteed to run in newer environments.
-///
-/// The target setting changes which JS features are downleveled and which are left intact. For example, an arrow
-/// function () => this will be turned into an equivalent function expression if target is `ES5` or lower.
-///
-/// Changing target also changes the default value of lib. You may “mix and match” target and lib settings
-/// as desired, but you could just set target for convenience.
-///
-/// For developer platforms like Node will have a certain baselines for the their target depending on their version.
-/// You can find a set of community organized TSConfigs at tsconfig/bases for common platforms and their versions.
+/// Loads the file at `path` and everything it extends. `chain` holds the canonical paths of the files
+/// currently being loaded, so that a file which ends up extending itself can be reported instead of
+/// recursing forever.
 ///
-/// The special `ESNext` value refers to the highest version your version of TypeScript supports. This setting should be
-/// used with caution, since it doesn’t mean the same thing between different TypeScript versions and can
-/// make upgrades less predictable.
-#[derive(Debug, PartialEq, Clone)]
-pub enum Target {
-    Es3,
-    Es5,
-    Es2015,
-    Es6,
-    Es2016,
-    Es7,
-    Es2017,
-    Es2018,
-    Es2019,
-    Es2020,
-    EsNext,
-    Other(String),
-}
-impl<'de> Deserialize<'de> for Target {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: Deserializer<'de>,
+/// Files are visited in order of precedence, so when `provenance` is given, the first file seen to set a
+/// compiler option is the one whose value ends up in the merged configuration.
+fn load_file_to_value<R: ConfigResolver + ?Sized>(
+    path: &Path,
+    resolver: &R,
+    chain: &mut Vec<PathBuf>,
+    mut provenance: Option<&mut HashMap<String, PathBuf>>,
+) -> Result<Value> {
+    let file_error = |source: ConfigError| ConfigError::FileError {
+        path: path.to_path_buf(),
+        source: Box::new(source),
+    };
+
+    let canonical = resolver.canonicalize(path).map_err(file_error)?;
+    check_cycle(chain, &canonical)?;
+
+    let s = resolver.read(path).map_err(file_error)?;
+    let mut value = parse_to_value(&s).map_err(|e| contents_error(path, chain, e))?;
+
+    if let (Some(provenance), Value::Object(options)) =
+        (provenance.as_deref_mut(), &value["compilerOptions"])
     {
-        let s = String::deserialize(deserializer)?;
-        let s = s.to_uppercase();
+        for (key, option) in options {
+            if !option.is_null() {
+                provenance
+                    .entry(key.clone())
+                    .or_insert_with(|| path.to_path_buf());
+            }
+        }
+    }
 
-        let d = match s.as_str() {
-            "ES5" => Target::Es5,
-            "ES2015" => Target::Es2015,
-            "ES6" => Target::Es6,
-            "ES2016" => Target::Es2016,
-            "ES7" => Target::Es7,
-            "ES2017" => Target::Es2017,
-            "ES2018" => Target::Es2018,
-            "ES2019" => Target::Es2019,
-            "ES2020" => Target::Es2020,
-            "ESNEXT" => Target::EsNext,
-            other => Target::Other(other.to_string()),
-        };
+    // `merge` only fills in keys that are still missing, so the last entry in the list
+    // has to be merged first for it to take precedence over the earlier ones.
+    chain.push(canonical);
+    for s in extends_of(&value).iter().rev() {
+        let extends_path = resolver.resolve_extends(path, s).map_err(file_error)?;
+        let extends_value =
+            load_file_to_value(&extends_path, resolver, chain, provenance.as_deref_mut())?;
+        merge(&mut value, extends_value);
+    }
+    chain.pop();
+    if chain.is_empty() {
+        substitute_config_dir(&mut value, path);
+    }
 
-        Ok(d)
+    Ok(value)
+}
+
+/// Checks the file at `path` and everything it extends for [TsConfig::check_file], adding every problem to
+/// `errors`. `chain` is used as in [load_file_to_value].
+fn check_chain<R: ConfigResolver + ?Sized>(
+    path: &Path,
+    resolver: &R,
+    chain: &mut Vec<PathBuf>,
+    errors: &mut Vec<ConfigError>,
+) {
+    let file_error = |source: ConfigError| ConfigError::FileError {
+        path: path.to_path_buf(),
+        source: Box::new(source),
+    };
+
+    let loaded = (|| {
+        let canonical = resolver.canonicalize(path).map_err(file_error)?;
+        check_cycle(chain, &canonical)?;
+        let s = resolver.read(path).map_err(file_error)?;
+        let value = parse_to_value(&s).map_err(|e| contents_error(path, chain, e))?;
+        TsConfig::deserialize(&value).map_err(|e| contents_error(path, chain, e.into()))?;
+        Ok((canonical, value))
+    })();
+    let (canonical, value) = match loaded {
+        Ok(loaded) => loaded,
+        Err(error) => return errors.push(error),
+    };
+
+    chain.push(canonical);
+    for s in extends_of(&value) {
+        match resolver.resolve_extends(path, &s) {
+            Ok(extends_path) => check_chain(&extends_path, resolver, chain, errors),
+            Err(error) => errors.push(file_error(error)),
+        }
     }
+    chain.pop();
 }
 
-/// Available definitions for built-in JS APIs.
-///
-/// TypeScript includes a default set of type definitions for built-in JS APIs (like Math), as well as type definitions for things found in browser environments (like document). TypeScript also includes APIs for newer JS features matching the target you specify; for example the definition for Map is available if target is ES6 or newer.
-///
-/// You may want to change these for a few reasons:
-///
-/// * Your program doesn't run in a browser, so you don’t want the "dom" type definitions
-/// * Your runtime platform provides certain JavaScript API objects (maybe through polyfills), but doesn't yet support the full syntax of a given ECMAScript version
-/// * You have polyfills or native implementations for some, but not all, of a higher level ECMAScript version
+/// The asynchronous counterpart of [load_file_to_value], boxed because it recurses.
+#[cfg(feature = "tokio")]
+fn load_file_to_value_async<'a>(
+    path: PathBuf,
+    chain: &'a mut Vec<PathBuf>,
+) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<Value>> + Send + 'a>> {
+    Box::pin(async move {
+        let file_error = |source: ConfigError| ConfigError::FileError {
+            path: path.clone(),
+            source: Box::new(source),
+        };
+
+        let canonical = tokio::fs::canonicalize(&path)
+            .await
+            .map_err(|e| file_error(e.into()))?;
+        check_cycle(chain, &canonical)?;
+
+        let s = tokio::fs::read_to_string(&path)
+            .await
+            .map_err(|e| file_error(e.into()))?;
+        let mut value = parse_to_value(&s).map_err(|e| contents_error(&path, chain, e))?;
+
+        chain.push(canonical);
+        for s in extends_of(&value).iter().rev() {
+            let extends_path = FsResolver.resolve_extends(&path, s).map_err(file_error)?;
+            let extends_value = load_file_to_value_async(extends_path, chain).await?;
+            merge(&mut value, extends_value);
+        }
+        chain.pop();
+        if chain.is_empty() {
+            substitute_config_dir(&mut value, &path);
+        }
+
+        Ok(value)
+    })
+}
+
+/// Expands the `${configDir}` template at the start of path values to the directory of `config_path`.
 ///
-#[derive(Debug, PartialEq, Clone)]
-pub enum Lib {
-    Es5,
-    Es2015,
-    Es6,
-    Es2016,
-    Es7,
-    Es2017,
-    Es2018,
-    Es2019,
-    Es2020,
-    EsNext,
-    Dom,
-    WebWorker,
-    ScriptHost,
-    DomIterable,
-    Es2015Core,
-    Es2015Generator,
-    Es2015Iterable,
-    Es2015Promise,
-    Es2015Proxy,
-    Es2015Reflect,
-    Es2015Symbol,
-    Es2015SymbolWellKnown,
-    Es2016ArrayInclude,
-    Es2017Object,
-    Es2017Intl,
-    Es2017SharedMemory,
-    Es2017String,
-    Es2017TypedArrays,
-    Es2018Intl,
-    Es2018Promise,
-    Es2018RegExp,
-    Es2019Array,
-    Es2019Object,
-    Es2019String,
-    Es2019Symbol,
-    Es2020String,
-    Es2020SymbolWellknown,
-    EsNextAsyncIterable,
-    EsNextArray,
-    EsNextIntl,
-    EsNextSymbol,
-    Other(String),
+/// As in TypeScript, this is the directory of the configuration file being loaded, not of the base file that
+/// wrote the template, which lets a shared base put output next to each project that extends it. Only
+/// `files`, `include`, `exclude`, `compilerOptions` and `watchOptions` are searched.
+fn substitute_config_dir(value: &mut Value, config_path: &Path) {
+    fn substitute(value: &mut Value, dir: &str) {
+        match value {
+            Value::String(s) => {
+                if let Some(rest) = s.strip_prefix("${configDir}") {
+                    *s = format!("{}{}", dir, rest);
+                }
+            }
+            Value::Array(values) => values.iter_mut().for_each(|v| substitute(v, dir)),
+            Value::Object(values) => values.values_mut().for_each(|v| substitute(v, dir)),
+            _ => {}
+        }
+    }
+
+    let dir = match config_path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir.to_string_lossy().into_owned(),
+        _ => ".".to_string(),
+    };
+    for key in &[
+        "files",
+        "include",
+        "exclude",
+        "compilerOptions",
+        "watchOptions",
+    ] {
+        if let Some(field) = value.get_mut(*key) {
+            substitute(field, &dir);
+        }
+    }
 }
 
-impl<'de> Deserialize<'de> for Lib {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        let s = String::deserialize(deserializer)?;
-        let s = s.to_uppercase();
+/// Wraps an error in the contents of the file at `path`, naming it as a base file unless it is the one at the
+/// start of `chain`.
+fn contents_error(path: &Path, chain: &[PathBuf], source: ConfigError) -> ConfigError {
+    let path = path.to_path_buf();
+    let source = Box::new(source);
+    if chain.is_empty() {
+        ConfigError::FileError { path, source }
+    } else {
+        ConfigError::InvalidExtends { path, source }
+    }
+}
 
-        let d = match s.as_str() {
-            "ES5" => Lib::Es5,
-            "ES2015" => Lib::Es2015,
-            "ES6" => Lib::Es6,
-            "ES2016" => Lib::Es2016,
-            "ES7" => Lib::Es7,
-            "ES2017" => Lib::Es2017,
-            "ES2018" => Lib::Es2018,
-            "ES2019" => Lib::Es2019,
-            "ES2020" => Lib::Es2020,
-            "ESNext" => Lib::EsNext,
-            "DOM" => Lib::Dom,
-            "WEBWORKER" => Lib::WebWorker,
-            "SCRIPTHOST" => Lib::ScriptHost,
-            "DOM.ITERABLE" => Lib::DomIterable,
-            "ES2015.CORE" => Lib::Es2015Core,
-            "ES2015.GENERATOR" => Lib::Es2015Generator,
-            "ES2015.ITERABLE" => Lib::Es2015Iterable,
-            "ES2015.PROMISE" => Lib::Es2015Promise,
-            "ES2015.PROXY" => Lib::Es2015Proxy,
-            "ES2015.REFLECT" => Lib::Es2015Reflect,
-            "ES2015.SYMBOL" => Lib::Es2015Symbol,
-            "ES2015.SYMBOL.WELLKNOWN" => Lib::Es2015SymbolWellKnown,
-            "ES2015.ARRAY.INCLUDE" => Lib::Es2016ArrayInclude,
-            "ES2015.OBJECT" => Lib::Es2017Object,
-            "ES2017INTL" => Lib::Es2017Intl,
-            "ES2015.SHAREDMEMORY" => Lib::Es2017SharedMemory,
-            "ES2017.STRING" => Lib::Es2017String,
-            "ES2017.TYPEDARRAYS" => Lib::Es2017TypedArrays,
-            "ES2018.INTL" => Lib::Es2018Intl,
-            "ES2018.PROMISE" => Lib::Es2018Promise,
-            "ES2018.REGEXP" => Lib::Es2018RegExp,
-            "ES2019.ARRAY" => Lib::Es2019Array,
-            "ES2019.OBJECT" => Lib::Es2019Object,
-            "ES2019.STRING" => Lib::Es2019String,
-            "ES2019.SYMBOL" => Lib::Es2019Symbol,
-            "ES2020.STRING" => Lib::Es2020String,
-            "ES2020.SYMBOL.WELLKNOWN" => Lib::Es2020SymbolWellknown,
-            "ESNEXT.ASYNCITERABLE" => Lib::EsNextAsyncIterable,
-            "ESNEXT.ARRAY" => Lib::EsNextArray,
-            "ESNEXT.INTL" => Lib::EsNextIntl,
-            "ESNEXT.SYMBOL" => Lib::EsNextSymbol,
-            other => Lib::Other(other.to_string()),
-        };
+/// Fails with [ConfigError::CircularExtends] if `canonical` is already being loaded further up the chain.
+fn check_cycle(chain: &[PathBuf], canonical: &Path) -> Result<()> {
+    match chain.iter().position(|p| p == canonical) {
+        Some(start) => {
+            let mut cycle = chain[start..].to_vec();
+            cycle.push(canonical.to_path_buf());
+            Err(ConfigError::CircularExtends(cycle))
+        }
+        None => Ok(()),
+    }
+}
 
-        Ok(d)
+/// The files named in a configuration's `extends` field, in the order they were written.
+fn extends_of(value: &Value) -> Vec<String> {
+    match &value["extends"] {
+        Value::String(s) => vec![s.clone()],
+        Value::Array(paths) => paths
+            .iter()
+            .filter_map(|p| p.as_str().map(String::from))
+            .collect(),
+        _ => Vec::new(),
     }
 }
 
-/// Sets the module system for the program.
+/// Locates and reads the configuration files named in `extends`.
 ///
-/// See the [Modules reference page](https://www.typescriptlang.org/docs/handbook/modules.html)
-/// for more information. You very likely want "CommonJS" for node projects.
-///
-/// Changing module affects moduleResolution which also has a reference page.
-///
-/// Here’s some example output for this file:
-///
-/// ```tsx
-/// // @filename: index.ts
-/// import { valueOfPi } from "./constants";
-///
-///
-/// export const twoPi = valueOfPi * 2;
-/// ```
-///
-/// ## CommonJS
+/// The default implementation follows TypeScript: specifiers starting with `.` or `/` are resolved relative to
+/// the extending file, anything else is looked up as a package in the `node_modules` directories above it.
+/// Implement this trait to change how inherited files are found, or override the filesystem methods to load
+/// configurations from somewhere other than the local disk, such as an in-memory or version-controlled tree.
+pub trait ConfigResolver {
+    /// Resolves `specifier`, as written in the `extends` field of the file at `config_path`, to a file path.
+    fn resolve_extends(&self, config_path: &Path, specifier: &str) -> Result<PathBuf> {
+        Ok(resolve_extends_path(self, config_path, specifier))
+    }
+
+    /// Reads the contents of the file at `path`.
+    fn read(&self, path: &Path) -> Result<String> {
+        Ok(std::fs::read_to_string(path)?)
+    }
+
+    /// Returns a form of `path` that is the same for every path to the same file, used to detect files that
+    /// extend themselves.
+    fn canonicalize(&self, path: &Path) -> Result<PathBuf> {
+        Ok(std::fs::canonicalize(path)?)
+    }
+
+    /// Whether `path` is an existing file.
+    fn is_file(&self, path: &Path) -> bool {
+        path.is_file()
+    }
+
+    /// Whether `path` is an existing directory.
+    fn is_dir(&self, path: &Path) -> bool {
+        path.is_dir()
+    }
+}
+
+/// The default [ConfigResolver], which looks up inherited files on the local filesystem.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct FsResolver;
+
+impl ConfigResolver for FsResolver {}
+
+fn resolve_extends_path<R: ConfigResolver + ?Sized>(
+    resolver: &R,
+    config_path: &Path,
+    specifier: &str,
+) -> PathBuf {
+    let config_dir = config_path.parent().unwrap_or_else(|| Path::new(""));
+
+    if specifier.starts_with('.') || Path::new(specifier).is_absolute() {
+        return config_file(resolver, config_dir.join(specifier));
+    }
+
+    // Package specifiers are either `name/sub/path` or `@scope/name/sub/path`.
+    let mut segments = specifier.splitn(if specifier.starts_with('@') { 3 } else { 2 }, '/');
+    let package_name = if specifier.starts_with('@') {
+        let scope = segments.next().unwrap_or_default();
+        format!("{}/{}", scope, segments.next().unwrap_or_default())
+    } else {
+        segments.next().unwrap_or_default().to_string()
+    };
+    let subpath = segments.next();
+
+    for dir in config_dir.ancestors() {
+        let package_dir = dir.join("node_modules").join(&package_name);
+        if resolver.is_dir(&package_dir) {
+            return resolve_package_file(resolver, &package_dir, subpath);
+        }
+    }
+
+    // Not a package either; fall back to treating the specifier as a relative path.
+    config_file(resolver, config_dir.join(specifier))
+}
+
+/// Picks the configuration file an `extends` path refers to, which for a directory is the tsconfig.json inside it.
 ///
-/// ```js
-/// "use strict";
-/// Object.defineProperty(exports, "__esModule", { value: true });
-/// exports.twoPi = void 0;
-/// const constants_1 = require("./constants");
-/// exports.twoPi = constants_1.valueOfPi * 2;
-/// ```
+/// As in TypeScript, `.json` is appended to a path that is neither an existing file nor a directory, unless it
+/// already ends in `.json`, so `./base` and `./tsconfig.base` both work.
+fn config_file<R: ConfigResolver + ?Sized>(resolver: &R, path: PathBuf) -> PathBuf {
+    if resolver.is_file(&path) || path.to_string_lossy().ends_with(".json") {
+        path
+    } else if resolver.is_dir(&path) {
+        path.join("tsconfig.json")
+    } else {
+        let mut path = path.into_os_string();
+        path.push(".json");
+        path.into()
+    }
+}
+
+fn resolve_package_file<R: ConfigResolver + ?Sized>(
+    resolver: &R,
+    package_dir: &Path,
+    subpath: Option<&str>,
+) -> PathBuf {
+    let manifest = resolver
+        .read(&package_dir.join("package.json"))
+        .ok()
+        .and_then(|s| serde_json::from_str::<Value>(&s).ok())
+        .unwrap_or(Value::Null);
+
+    let export_key = match subpath {
+        Some(subpath) => format!("./{}", subpath),
+        None => ".".to_string(),
+    };
+    let exported = match &manifest["exports"] {
+        Value::String(target) if subpath.is_none() => Some(target.as_str()),
+        Value::Object(exports) => exports.get(&export_key).and_then(export_target),
+        _ => None,
+    };
+    if let Some(target) = exported {
+        return package_dir.join(target);
+    }
+
+    match subpath {
+        Some(subpath) => {
+            let path = package_dir.join(subpath);
+            if path.extension().is_none() && !resolver.is_file(&path) {
+                path.with_extension("json")
+            } else {
+                path
+            }
+        }
+        None => match manifest["tsconfig"].as_str() {
+            Some(tsconfig) => package_dir.join(tsconfig),
+            None => package_dir.join("tsconfig.json"),
+        },
+    }
+}
+
+/// Picks a file out of a `package.json` `exports` entry, which is either a path or a map of conditions.
+fn export_target(entry: &Value) -> Option<&str> {
+    match entry {
+        Value::String(target) => Some(target),
+        Value::Object(conditions) => conditions.values().find_map(export_target),
+        _ => None,
+    }
+}
+
+/// Parse a JSON string into a single [serde_json::Value].
 ///
-/// ## UMD
+/// The 'extends' field will be ignored. Comments and trailing commas are both allowed, although they are not valid JSON.
+/// With the `preserve_order` feature, the keys of every object are kept in the order they are written.
 ///
-/// ```js
-/// (function (factory) {
-///     if (typeof module === "object" && typeof module.exports === "object") {
-///         var v = factory(require, exports);
-///         if (v !== undefined) module.exports = v;
-///     }
-///     else if (typeof define === "function" && define.amd) {
-///         define(["require", "exports", "./constants"], factory);
-///     }
-/// })(function (require, exports) {
-///     "use strict";
-///     Object.defineProperty(exports, "__esModule", { value: true });
-///     exports.twoPi = void 0;
-///     const constants_1 = require("./constants");
-///     exports.twoPi = constants_1.valueOfPi * 2;
-/// });
+/// ## Example
 /// ```
+/// use tsconfig::parse_to_value;
+/// use serde_json::Value;
 ///
-/// ## AMD
 ///
-/// ```js
-/// define(["require", "exports", "./constants"], function (require, exports, constants_1) {
-///     "use strict";
-///     Object.defineProperty(exports, "__esModule", { value: true });
-///     exports.twoPi = void 0;
-///     exports.twoPi = constants_1.valueOfPi * 2;
-/// });
-/// ```
+/// let json = r#"{"compilerOptions": {"jsx": /*here's a comment*/ "react-jsx"},}"#;
 ///
-/// ## System
+/// let config = parse_to_value(json).unwrap();
+/// assert_eq!(config["compilerOptions"]["jsx"], Value::String("react-jsx".to_string()));     
+///```
 ///
-/// ```js
-/// System.register(["./constants"], function (exports_1, context_1) {
-///     "use strict";
-///     var constants_1, twoPi;
-///     var __moduleName = context_1 && context_1.id;
-///     return {
-///         setters: [
-///             function (constants_1_1) {
-///                 constants_1 = constants_1_1;
-///             }
-///         ],
-///         execute: function () {
-///             exports_1("twoPi", twoPi = constants_1.valueOfPi * 2);
-///         }
-///     };
-/// });
-/// ```
+pub fn parse_to_value(json: &str) -> Result<Value> {
+    let stripped = sanitize_json(json)?;
+    let r: Value = serde_json::from_str(&stripped).map_err(|e| json_error(json, &stripped, e))?;
+    Ok(r)
+}
+
+/// Sets a single option in the source of a configuration file, keeping its comments and formatting.
 ///
-/// ## ESNext
+/// `key_path` names the option from the top of the file, such as `["compilerOptions", "strict"]`. An existing
+/// value is replaced in place. A missing option is added after the last member of the innermost object that
+/// exists, creating any objects along the path that are missing, and follows the indentation of the members
+/// around it. Returns [ConfigError::NotAnObject] if the path runs through a value that is not an object.
 ///
-/// ```js
-/// import { valueOfPi } from "./constants";
-/// export const twoPi = valueOfPi * 2;
+/// ## Example
 /// ```
+/// use serde_json::json;
+/// use tsconfig::set_option_in_source;
 ///
-/// ## ES2020
+/// let source = r#"{
+///   // Checked by CI.
+///   "compilerOptions": {
+///     "strict": false, // for now
+///   },
+/// }"#;
 ///
-/// ```js
-/// import { valueOfPi } from "./constants";
-/// export const twoPi = valueOfPi * 2;
+/// let updated = set_option_in_source(source, &["compilerOptions", "strict"], &json!(true)).unwrap();
+/// let updated = set_option_in_source(&updated, &["compilerOptions", "outDir"], &json!("dist")).unwrap();
+/// assert_eq!(
+///     updated,
+///     r#"{
+///   // Checked by CI.
+///   "compilerOptions": {
+///     "strict": true, // for now
+///     "outDir": "dist",
+///   },
+/// }"#
+/// );
 /// ```
+pub fn set_option_in_source(source: &str, key_path: &[&str], value: &Value) -> Result<String> {
+    if !parse_to_value(source)?.is_object() {
+        return Err(ConfigError::ParseError(serde::de::Error::custom(
+            "expected the configuration to be an object",
+        )));
+    }
+    if key_path.is_empty() {
+        return Ok(serde_json::to_string_pretty(value)?);
+    }
+
+    // Scan a copy with the comments blanked out. Offsets into it are also offsets into the source.
+    let stripped = strip_comments(source)?;
+    let mut scanner = Scanner {
+        json: stripped.as_bytes(),
+        pos: 0,
+    };
+    scanner.skip_whitespace();
+    let mut updated = source.to_string();
+
+    for (depth, key) in key_path.iter().enumerate() {
+        let open = scanner.pos;
+        let (members, close) = scanner.members();
+        let rest = &key_path[depth + 1..];
+
+        match members.iter().rev().find(|member| member.key == *key) {
+            Some(member) if rest.is_empty() => {
+                updated.replace_range(
+                    member.value_start..member.value_end,
+                    &serde_json::to_string(value)?,
+                );
+                return Ok(updated);
+            }
+            Some(member) if stripped.as_bytes()[member.value_start] == b'{' => {
+                scanner.pos = member.value_start;
+            }
+            Some(_) => return Err(ConfigError::NotAnObject(key_path[..=depth].join("."))),
+            None => {
+                let nested = rest.iter().rev().fold(value.clone(), |value, key| {
+                    let mut object = serde_json::Map::new();
+                    object.insert(key.to_string(), value);
+                    Value::Object(object)
+                });
+                let member = format!(
+                    "{}: {}",
+                    serde_json::to_string(key)?,
+                    serde_json::to_string(&nested)?
+                );
+                insert_member(&mut updated, &stripped, open, close, &members, &member);
+                return Ok(updated);
+            }
+        }
+    }
+    unreachable!("the last key either replaces or inserts a value")
+}
+
+/// A member of a JSON object, located by byte offsets into the text it was scanned from.
+struct Member {
+    key: String,
+    key_start: usize,
+    value_start: usize,
+    value_end: usize,
+}
+
+/// Walks JSON whose comments have been blanked out, keeping track of where each value lies.
 ///
-/// ## None
-///
-/// ```js
-/// "use strict";
-/// Object.defineProperty(exports, "__esModule", { value: true });
-/// exports.twoPi = void 0;
-/// const constants_1 = require("./constants");
-/// exports.twoPi = constants_1.valueOfPi * 2;
+/// The text must already be known to be valid, apart from trailing commas.
+struct Scanner<'a> {
+    json: &'a [u8],
+    pos: usize,
+}
+
+impl Scanner<'_> {
+    fn skip_whitespace(&mut self) {
+        while self.json.get(self.pos).is_some_and(u8::is_ascii_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    fn skip_string(&mut self) {
+        self.pos += 1;
+        while let Some(&byte) = self.json.get(self.pos) {
+            self.pos += if byte == b'\\' { 2 } else { 1 };
+            if byte == b'"' {
+                return;
+            }
+        }
+    }
+
+    fn skip_value(&mut self) {
+        match self.json.get(self.pos) {
+            Some(b'"') => self.skip_string(),
+            Some(b'{') | Some(b'[') => {
+                let mut depth = 0;
+                while let Some(&byte) = self.json.get(self.pos) {
+                    match byte {
+                        b'"' => {
+                            self.skip_string();
+                            continue;
+                        }
+                        b'{' | b'[' => depth += 1,
+                        b'}' | b']' => depth -= 1,
+                        _ => {}
+                    }
+                    self.pos += 1;
+                    if depth == 0 {
+                        return;
+                    }
+                }
+            }
+            _ => {
+                while self.json.get(self.pos).is_some_and(|byte| {
+                    !matches!(byte, b',' | b'}' | b']') && !byte.is_ascii_whitespace()
+                }) {
+                    self.pos += 1;
+                }
+            }
+        }
+    }
+
+    /// Reads the array starting at the current position, returning the offset of each element.
+    fn elements(&mut self) -> Vec<usize> {
+        let mut elements = Vec::new();
+        self.pos += 1;
+        loop {
+            self.skip_whitespace();
+            match self.json.get(self.pos) {
+                Some(b',') => self.pos += 1,
+                Some(b']') | None => return elements,
+                Some(_) => {
+                    elements.push(self.pos);
+                    self.skip_value();
+                }
+            }
+        }
+    }
+
+    /// Finds the first key set twice in the same object, anywhere within the value at the current position.
+    ///
+    /// Returns the key's path, built onto `path`, the path of the current value.
+    fn duplicate_key(&mut self, path: &str) -> Option<String> {
+        let child = |key: &str| {
+            if path.is_empty() {
+                key.to_string()
+            } else {
+                format!("{}.{}", path, key)
+            }
+        };
+        let children: Vec<(String, usize)> = match self.json.get(self.pos) {
+            Some(b'{') => {
+                let (members, _) = self.members();
+                let mut seen = HashSet::new();
+                if let Some(member) = members.iter().find(|member| !seen.insert(&member.key)) {
+                    return Some(child(&member.key));
+                }
+                members
+                    .iter()
+                    .map(|member| (child(&member.key), member.value_start))
+                    .collect()
+            }
+            Some(b'[') => self
+                .elements()
+                .into_iter()
+                .enumerate()
+                .map(|(i, start)| (format!("{}[{}]", path, i), start))
+                .collect(),
+            _ => return None,
+        };
+        children.into_iter().find_map(|(path, start)| {
+            self.pos = start;
+            self.duplicate_key(&path)
+        })
+    }
+
+    /// Reads the object starting at the current position, returning its members and the offset of its closing brace.
+    fn members(&mut self) -> (Vec<Member>, usize) {
+        let mut members = Vec::new();
+        self.pos += 1;
+        loop {
+            self.skip_whitespace();
+            match self.json.get(self.pos) {
+                Some(b'"') => {
+                    let key_start = self.pos;
+                    self.skip_string();
+                    let key =
+                        serde_json::from_slice(&self.json[key_start..self.pos]).unwrap_or_default();
+                    self.skip_whitespace();
+                    self.pos += 1;
+                    self.skip_whitespace();
+                    let value_start = self.pos;
+                    self.skip_value();
+                    members.push(Member {
+                        key,
+                        key_start,
+                        value_start,
+                        value_end: self.pos,
+                    });
+                }
+                Some(b',') => self.pos += 1,
+                _ => return (members, self.pos),
+            }
+        }
+    }
+}
+
+/// Adds `member` to the object spanning `open..=close` in `source`, after its last member.
+///
+/// A member on a line of its own gets a new line with the same indentation, and keeps a trailing comma if the
+/// object uses them. Objects written on a single line stay on one line.
+fn insert_member(
+    source: &mut String,
+    stripped: &str,
+    open: usize,
+    close: usize,
+    members: &[Member],
+    member: &str,
+) {
+    let last = match members.last() {
+        Some(last) => last,
+        None => {
+            let text = if stripped[open..close].contains('\n') {
+                format!("\n{}  {}", line_indent(source, open), member)
+            } else {
+                member.to_string()
+            };
+            source.insert_str(open + 1, &text);
+            return;
+        }
+    };
+
+    let mut after = last.value_end;
+    let rest = &stripped[after..close];
+    let trailing_comma = rest.trim_start().starts_with(',');
+    if trailing_comma {
+        after += rest.len() - rest.trim_start().len() + 1;
+    }
+    let line_end = stripped[after..close]
+        .find('\n')
+        .map(|i| after + i)
+        .filter(|&end| stripped[after..end].trim().is_empty())
+        .map(|end| {
+            if stripped[..end].ends_with('\r') {
+                end - 1
+            } else {
+                end
+            }
+        });
+
+    let indent = line_indent(source, last.key_start).to_string();
+    match (line_end, trailing_comma) {
+        (Some(end), true) => source.insert_str(end, &format!("\n{}{},", indent, member)),
+        (Some(end), false) => {
+            source.insert_str(end, &format!("\n{}{}", indent, member));
+            source.insert(last.value_end, ',');
+        }
+        (None, true) => source.insert_str(after, &format!(" {},", member)),
+        (None, false) => source.insert_str(last.value_end, &format!(", {}", member)),
+    }
+}
+
+/// Returns the whitespace that starts the line containing the offset `at`.
+fn line_indent(source: &str, at: usize) -> &str {
+    let start = source[..at].rfind('\n').map_or(0, |i| i + 1);
+    let line = &source[start..at];
+    &line[..line.len() - line.trim_start().len()]
+}
+
+/// Turns the contents of a configuration file into plain JSON by blanking out comments and trailing commas.
+///
+/// Every entry point goes through here. Characters are replaced rather than removed, so that positions in the
+/// result line up with `raw` and [json_error] can report errors against the original source.
+fn sanitize_json(raw: &str) -> Result<String> {
+    Ok(strip_trailing_commas(&strip_comments(raw)?))
+}
+
+/// Blanks out the comments in `raw`, keeping every other character where it is.
+///
+/// A leading byte order mark, which editors on Windows often write, is blanked out as well.
+fn strip_comments(raw: &str) -> Result<String> {
+    let mut stripped = String::with_capacity(raw.len());
+    let raw = match raw.strip_prefix('\u{feff}') {
+        Some(rest) => {
+            stripped.push_str("   ");
+            rest
+        }
+        None => raw,
+    };
+    StripComments::new(raw.as_bytes()).read_to_string(&mut stripped)?;
+    Ok(stripped)
+}
+
+/// Converts a JSON syntax error found in `stripped` into a [ConfigError::InvalidJson] pointing into `source`.
+///
+/// Other errors, such as a value of the wrong type, are returned as [ConfigError::ParseError].
+fn json_error(source: &str, stripped: &str, err: serde_json::Error) -> ConfigError {
+    if !(err.is_syntax() || err.is_eof()) {
+        return ConfigError::ParseError(err);
+    }
+
+    // Comments and trailing commas are blanked out byte for byte, so an offset into the stripped text is
+    // also an offset into the source. Block comments lose their line breaks though, so the line and
+    // column have to be recomputed from that offset.
+    let line_start: usize = stripped
+        .split('\n')
+        .take(err.line().saturating_sub(1))
+        .map(|line| line.len() + 1)
+        .sum();
+    let mut offset = (line_start + err.column().saturating_sub(1)).min(source.len());
+    while !source.is_char_boundary(offset) {
+        offset -= 1;
+    }
+
+    let before = &source[..offset];
+    let line_begin = before.rfind('\n').map_or(0, |i| i + 1);
+    let snippet = source[line_begin..].lines().next().unwrap_or_default();
+
+    ConfigError::InvalidJson {
+        line: before.matches('\n').count() + 1,
+        column: offset - line_begin + 1,
+        snippet: snippet.to_string(),
+        source: err,
+    }
+}
+
+/// Blanks out commas that directly precede a closing `}` or `]`, which TypeScript allows but JSON does not.
+///
+/// Commas inside string literals are left alone. Comments must already have been stripped. The commas are
+/// replaced by spaces rather than removed so that positions in the output match the original source.
+fn strip_trailing_commas(json: &str) -> String {
+    let mut out = String::with_capacity(json.len());
+    let mut chars = json.char_indices();
+    let mut in_string = false;
+
+    while let Some((i, c)) = chars.next() {
+        if in_string {
+            match c {
+                '\\' => {
+                    out.push(c);
+                    if let Some((_, escaped)) = chars.next() {
+                        out.push(escaped);
+                    }
+                    continue;
+                }
+                '"' => in_string = false,
+                _ => {}
+            }
+        } else {
+            match c {
+                '"' => in_string = true,
+                ',' => {
+                    let next = json[i + 1..].trim_start().chars().next();
+                    if let Some('}') | Some(']') = next {
+                        out.push(' ');
+                        continue;
+                    }
+                }
+                _ => {}
+            }
+        }
+        out.push(c);
+    }
+
+    out
+}
+
+/// The value of the `extends` field.
+///
+/// Since TypeScript 5.0 a configuration may inherit from several files at once. When a list is given,
+/// later entries override earlier ones, and the file doing the extending overrides all of them.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum Extends {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
+/// Project references setting  
+///
+/// Project references are a way to structure your TypeScript programs into smaller pieces. Using
+/// Project References can greatly improve build and editor interaction times, enforce logical separation
+/// between components, and organize your code in new and improved ways.
+///
+/// You can read more about how references works in the Project References section of [the handbook](https://www.typescriptlang.org/docs/handbook/project-references.html).
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum References {
+    Bool(bool),
+    References(Vec<Reference>),
+}
+
+impl<'de> Deserialize<'de> for References {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        match Value::deserialize(deserializer)? {
+            Value::Bool(b) => Ok(References::Bool(b)),
+            Value::Array(entries) => entries
+                .into_iter()
+                .enumerate()
+                .map(|(i, entry)| {
+                    serde_json::from_value(entry).map_err(|e| {
+                        D::Error::custom(format!("invalid project reference at index {}: {}", i, e))
+                    })
+                })
+                .collect::<Result<_, _>>()
+                .map(References::References),
+            other => Err(D::Error::custom(format!(
+                "expected a boolean or a list of project references, found {}",
+                other
+            ))),
+        }
+    }
+}
+
+/// Project references setting  
+///
+/// Project references are a way to structure your TypeScript programs into smaller pieces. Using
+/// Project References can greatly improve build and editor interaction times, enforce logical separation
+/// between components, and organize your code in new and improved ways.
+///
+/// You can read more about how references works in the Project References section of [the handbook](https://www.typescriptlang.org/docs/handbook/project-references.html).
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct Reference {
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prepend: Option<bool>,
+    /// Any other keys in the entry, kept as they were written.
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+/// Defines how automatic type acquisition behaves.
+///
+/// When you have a JavaScript project in your editor, TypeScript will provide types for your node_modules automatically
+/// using the DefinitelyTyped set of @types definitions. This is called automatic type acquisition, and you can customize
+/// it using the typeAcquisition object in your configuration.
+///
+/// If you would like to disable or customize this feature, create a jsconfig.json in the root of your project:
+///
+/// ```json
+/// {
+///   "typeAcquisition": {
+///     "enable": false
+///   }
+/// }
 /// ```
-#[derive(Debug, Clone, PartialEq)]
-pub enum Module {
-    CommonJs,
-    Es6,
-    Es2015,
-    Es2020,
-    None,
-    Umd,
-    Amd,
-    System,
-    EsNext,
-    Other(String),
+///
+/// If you have a specific module which should be included (but isn’t in node_modules):
+///
+/// ```json
+/// {
+///   "typeAcquisition": {
+///     "include": ["jest"]
+///   }
+/// }
+/// ```
+///
+/// If a module should not be automatically acquired, for example if the library is available in your node_modules but your team has agreed to not use it:
+///
+/// ```json
+/// {
+///   "typeAcquisition": {
+///     "exclude": ["jquery"]
+///   }
+/// }
+/// ```
+///
+/// In TypeScript 4.1, we added the ability to disable the special-casing where a filename would trigger type acquisition:
+///
+/// ```json
+/// {
+///   "typeAcquisition": {
+///     "disableFilenameBasedTypeAcquisition": true
+///   }
+/// }
+/// ```
+///
+/// This means that having a file like jquery.js in your project would not automatically download the types for JQuery from DefinitelyTyped.
+///
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum TypeAcquisition {
+    Bool(bool),
+    #[serde(rename_all = "camelCase")]
+    Object {
+        #[serde(default)]
+        enable: bool,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        include: Option<Vec<String>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        exclude: Option<Vec<String>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        disable_filename_based_type_acquisition: Option<bool>,
+    },
+}
+
+impl TypeAcquisition {
+    /// Layers these settings over `base` for [TsConfig::merged_with]. Two objects are merged option by option,
+    /// and otherwise the settings here replace the base's.
+    fn merged_with(self, base: TypeAcquisition) -> TypeAcquisition {
+        match (self, base) {
+            (
+                TypeAcquisition::Object {
+                    enable,
+                    include,
+                    exclude,
+                    disable_filename_based_type_acquisition,
+                },
+                TypeAcquisition::Object {
+                    include: base_include,
+                    exclude: base_exclude,
+                    disable_filename_based_type_acquisition: base_disable,
+                    ..
+                },
+            ) => TypeAcquisition::Object {
+                enable,
+                include: include.or(base_include),
+                exclude: exclude.or(base_exclude),
+                disable_filename_based_type_acquisition: disable_filename_based_type_acquisition
+                    .or(base_disable),
+            },
+            (type_acquisition, _) => type_acquisition,
+        }
+    }
+}
+
+/// Settings for how `tsc --watch` watches files and directories, read from the top-level `"watchOptions"` object.
+///
+/// ## Example
+/// ```
+/// use tsconfig::{TsConfig, WatchDirectory, WatchFile};
+///
+/// let json = r#"{"watchOptions": {"watchFile": "useFsEvents", "watchDirectory": "dynamicPriorityPolling"}}"#;
+/// let watch_options = TsConfig::parse_str(json).unwrap().watch_options.unwrap();
+///
+/// assert_eq!(watch_options.watch_file, Some(WatchFile::UseFsEvents));
+/// assert_eq!(watch_options.watch_directory, Some(WatchDirectory::DynamicPriorityPolling));
+/// ```
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchOptions {
+    /// The strategy for watching individual files.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub watch_file: Option<WatchFile>,
+    /// The strategy for watching directory trees on systems without recursive file watching.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub watch_directory: Option<WatchDirectory>,
+    /// The polling strategy to fall back to when the system runs out of native file watchers.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fallback_polling: Option<FallbackPolling>,
+    /// Report directory changes synchronously instead of waiting for them to settle.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub synchronous_watch_directory: Option<bool>,
+    /// Directories, or patterns of directories, that should not be watched.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclude_directories: Option<Vec<String>>,
+    /// Files, or patterns of files, that should not be watched.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclude_files: Option<Vec<String>>,
+}
+
+impl WatchOptions {
+    /// Layers these options over `base` for [TsConfig::merged_with], keeping each option set here.
+    fn merged_with(self, base: WatchOptions) -> WatchOptions {
+        WatchOptions {
+            watch_file: self.watch_file.or(base.watch_file),
+            watch_directory: self.watch_directory.or(base.watch_directory),
+            fallback_polling: self.fallback_polling.or(base.fallback_polling),
+            synchronous_watch_directory: self
+                .synchronous_watch_directory
+                .or(base.synchronous_watch_directory),
+            exclude_directories: self.exclude_directories.or(base.exclude_directories),
+            exclude_files: self.exclude_files.or(base.exclude_files),
+        }
+    }
+}
+
+/// Strategies for `watchOptions.watchFile`.
+#[derive(Deserialize, Serialize, Debug, PartialEq, Copy, Clone, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum WatchFile {
+    /// Check every file for changes several times a second at a fixed interval
+    FixedPollingInterval,
+    /// Check every file for changes, but check hot files more often than cold ones
+    PriorityPollingInterval,
+    /// Poll files less often the longer they go without changing
+    DynamicPriorityPolling,
+    /// Poll a fixed number of files at a time
+    FixedChunkSizePolling,
+    /// Use the operating system's native file change events
+    UseFsEvents,
+    /// Listen for change events on each file's parent directory
+    UseFsEventsOnParentDirectory,
+}
+
+/// Strategies for `watchOptions.watchDirectory`.
+#[derive(Deserialize, Serialize, Debug, PartialEq, Copy, Clone, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum WatchDirectory {
+    /// Use the operating system's native directory change events
+    UseFsEvents,
+    /// Check every directory for changes several times a second at a fixed interval
+    FixedPollingInterval,
+    /// Poll directories less often the longer they go without changing
+    DynamicPriorityPolling,
+    /// Poll a fixed number of directories at a time
+    FixedChunkSizePolling,
 }
 
-impl<'de> Deserialize<'de> for Module {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        let s = String::deserialize(deserializer)?;
-        let s = s.to_uppercase();
+/// Strategies for `watchOptions.fallbackPolling`.
+#[derive(Deserialize, Serialize, Debug, PartialEq, Copy, Clone, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum FallbackPolling {
+    /// Check every file for changes several times a second at a fixed interval
+    FixedInterval,
+    /// Check every file for changes, but check hot files more often than cold ones
+    PriorityInterval,
+    /// Poll files less often the longer they go without changing
+    DynamicPriority,
+    /// Poll a fixed number of files at a time
+    FixedChunkSize,
+}
+
+/// These options make up the bulk of TypeScript’s configuration and it covers how the language should work.
+///
+/// Every option is exposed as a public field named after the snake_case form of its TypeScript name, so
+/// `"noImplicitAny"` is read through `no_implicit_any`. Options that were not set in the file are `None`.
+///
+/// ## Example
+/// ```
+/// use std::collections::HashMap;
+/// use tsconfig::{CompilerOptions, Target, TsConfig};
+///
+/// let json = r#"{"compilerOptions": {"target": "es2015", "paths": {"@app/*": ["src/*"]}}}"#;
+/// let config = TsConfig::parse_str(json).unwrap();
+/// let options: CompilerOptions = config.compiler_options.unwrap();
+///
+/// assert_eq!(options.target, Some(Target::Es2015));
+/// let paths: &HashMap<String, Vec<String>> = options.paths.as_ref().unwrap();
+/// assert_eq!(paths["@app/*"], vec!["src/*".to_string()]);
+/// assert_eq!(options.strict, None);
+/// ```
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CompilerOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_js: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub check_js: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub composite: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub declaration: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub declaration_map: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub downlevel_iteration: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub import_helpers: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub incremental: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub isolated_modules: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verbatim_module_syntax: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jsx: Option<Jsx>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lib: Option<Vec<Lib>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub module: Option<Module>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_emit: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub out_dir: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub out_file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remove_comments: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub root_dir: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_map: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<Target>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ts_build_info_file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub always_strict: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_implicit_any: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_implicit_this: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strict: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strict_bind_call_apply: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strict_function_types: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strict_null_checks: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strict_property_initialization: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub use_unknown_in_catch_variables: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strict_builtin_iterator_return: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_synthetic_default_imports: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_umd_global_access: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub es_module_interop: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub module_resolution: Option<ModuleResolutionMode>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub module_detection: Option<ModuleDetection>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paths: Option<HashMap<String, Vec<String>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preserve_symlinks: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_arbitrary_extensions: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_importing_ts_extensions: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub custom_conditions: Option<Vec<String>>,
+    /// Suffixes tried, in order, when resolving a module, such as `[".ios", ".native", ""]` for React Native.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub module_suffixes: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolve_package_json_exports: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolve_package_json_imports: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub root_dirs: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub type_roots: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub types: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inline_source_map: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inline_sources: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub map_root: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_root: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_fallthrough_cases_in_switch: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_implicit_returns: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_property_access_from_index_signature: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_unchecked_indexed_access: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_unused_locals: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_implicit_override: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exact_optional_property_types: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_unchecked_side_effect_imports: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub emit_decorator_metadata: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub experimental_decorators: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_unreachable_code: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_unused_labels: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assume_changes_only_affect_direct_dependencies: Option<bool>,
+    #[deprecated]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub charset: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub declaration_dir: Option<String>,
+    #[deprecated]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diagnostics: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disable_referenced_project_load: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disable_size_limit: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disable_solution_searching: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disable_source_of_project_reference_redirect: Option<bool>,
+    #[serde(rename = "emitBOM")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub emit_bom: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub emit_declaration_only: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explain_files: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extended_diagnostics: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub force_consistent_casing_in_file_names: Option<bool>,
+    // XXX: Is generateCpuProfile available from tsconfig? Or just the CLI?
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub generate_cpu_profile: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub imports_not_used_as_values: Option<ImportsNotUsedAsValues>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jsx_factory: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jsx_fragment_factory: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jsx_import_source: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keyof_strings_only: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub list_emitted_files: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub list_files: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_node_module_js_depth: Option<u32>,
+    /// The line ending to use when emitting files.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_line: Option<NewLine>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_emit_helpers: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_emit_on_error: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_error_truncation: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_implicit_use_strict: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_lib: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_resolve: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_strict_generic_checks: Option<bool>,
+    #[deprecated]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub out: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preserve_const_enums: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub react_namespace: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolve_json_module: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skip_default_lib_check: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skip_lib_check: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strip_internal: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suppress_excess_property_errors: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suppress_implicit_any_index_errors: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trace_resolution: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub use_define_for_class_fields: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preserve_watch_output: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plugins: Option<Vec<Plugin>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pretty: Option<bool>,
+    /// Kept for older configuration files; TypeScript reads this from [WatchOptions::fallback_polling].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fallback_polling: Option<String>,
+    /// Kept for older configuration files; TypeScript reads this from [WatchOptions::watch_directory].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub watch_directory: Option<String>,
+    /// Kept for older configuration files; TypeScript reads this from [WatchOptions::watch_file].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub watch_file: Option<String>,
+
+    /// Options this crate does not model, keyed by their name in the file.
+    ///
+    /// These are kept so that newer or misspelled options can be inspected, and are written back out on serialization.
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+impl CompilerOptions {
+    /// Parse a JSON string holding just a `compilerOptions` object into [CompilerOptions].
+    ///
+    /// Comments and trailing commas are allowed, as in [TsConfig::parse_str].
+    ///
+    /// ## Example
+    /// ```
+    /// use tsconfig::{CompilerOptions, Jsx};
+    ///
+    /// let json = r#"{"jsx": "react-jsx", "strict": true, /* shared */}"#;
+    /// let options = CompilerOptions::parse_str(json).unwrap();
+    ///
+    /// assert_eq!(options.jsx, Some(Jsx::ReactJsx));
+    /// assert_eq!(options.strict, Some(true));
+    /// ```
+    pub fn parse_str(json: &str) -> Result<CompilerOptions> {
+        let stripped = sanitize_json(json)?;
+        let r: CompilerOptions =
+            serde_json::from_str(&stripped).map_err(|e| json_error(json, &stripped, e))?;
+        Ok(r)
+    }
+
+    /// Returns the options under `compilerOptions` that this crate does not recognise.
+    ///
+    /// This can be used to warn about misspelled option names, or to read options introduced in newer
+    /// TypeScript versions.
+    ///
+    /// ## Example
+    /// ```
+    /// use tsconfig::TsConfig;
+    /// use serde_json::Value;
+    ///
+    /// let json = r#"{"compilerOptions": {"strict": true, "stritc": false}}"#;
+    /// let config = TsConfig::parse_str(json).unwrap();
+    /// let unknown = config.compiler_options.unwrap().unknown_options().clone();
+    ///
+    /// assert_eq!(unknown.len(), 1);
+    /// assert_eq!(unknown["stritc"], Value::Bool(false));
+    /// ```
+    pub fn unknown_options(&self) -> &HashMap<String, Value> {
+        &self.extra
+    }
+
+    /// Returns a copy of these options with unset values replaced by the defaults TypeScript would use.
+    ///
+    /// `target` is used when no target is set. The remaining defaults are derived the way `tsc` derives them:
+    ///
+    /// * `module` is `commonjs` for ES3 and ES5 targets, and `es6` otherwise.
+    /// * `moduleResolution` is `node10` for `commonjs`, `node16` for `node16` and `node18`,
+    ///   `nodenext` for `nodenext`, and `classic` for every other module kind.
+    /// * `useDefineForClassFields` is enabled for ES2022 and later targets.
+    /// * `esModuleInterop` is enabled for the `node16`, `node18` and `nodenext` module kinds, and
+    ///   `allowSyntheticDefaultImports` follows it, as well as `module: system` and `moduleResolution: bundler`.
+    /// * The individual strictness checks follow `strict`, which itself defaults to `false`.
+    /// * `declaration` and `incremental` follow `composite`.
+    ///
+    /// Options with unrecognised values (`Other`) are left alone rather than guessed at.
+    ///
+    /// ## Example
+    /// ```
+    /// use tsconfig::{CompilerOptions, Module, ModuleResolutionMode, Target};
+    ///
+    /// let options = CompilerOptions::default().with_defaults(Target::Es5);
+    /// assert_eq!(options.module, Some(Module::CommonJs));
+    /// assert_eq!(options.module_resolution, Some(ModuleResolutionMode::Node10));
+    /// assert_eq!(options.use_define_for_class_fields, Some(false));
+    /// ```
+    pub fn with_defaults(&self, target: Target) -> CompilerOptions {
+        let mut options = self.clone();
+
+        let year = options.target.get_or_insert(target).era();
+        let module = options
+            .module
+            .get_or_insert(match year {
+                Some(year) if year < 2015 => Module::CommonJs,
+                _ => Module::Es6,
+            })
+            .clone();
+
+        if options.module_resolution.is_none() {
+            options.module_resolution = match module {
+                Module::CommonJs => Some(ModuleResolutionMode::Node10),
+                Module::Node16 | Module::Node18 => Some(ModuleResolutionMode::Node16),
+                Module::NodeNext => Some(ModuleResolutionMode::NodeNext),
+                Module::Other(_) => None,
+                _ => Some(ModuleResolutionMode::Classic),
+            };
+        }
+
+        if options.use_define_for_class_fields.is_none() {
+            options.use_define_for_class_fields = year.map(|year| year >= 2022);
+        }
+
+        let es_module_interop = *options.es_module_interop.get_or_insert(matches!(
+            module,
+            Module::Node16 | Module::Node18 | Module::NodeNext
+        ));
+        options.allow_synthetic_default_imports.get_or_insert(
+            es_module_interop
+                || module == Module::System
+                || options.module_resolution == Some(ModuleResolutionMode::Bundler),
+        );
+
+        let strict = *options.strict.get_or_insert(false);
+        for check in [
+            &mut options.always_strict,
+            &mut options.no_implicit_any,
+            &mut options.no_implicit_this,
+            &mut options.strict_bind_call_apply,
+            &mut options.strict_function_types,
+            &mut options.strict_null_checks,
+            &mut options.strict_property_initialization,
+            &mut options.use_unknown_in_catch_variables,
+            &mut options.strict_builtin_iterator_return,
+        ]
+        .iter_mut()
+        {
+            check.get_or_insert(strict);
+        }
+
+        let composite = options.composite.unwrap_or(false);
+        options.declaration.get_or_insert(composite);
+        options.incremental.get_or_insert(composite);
+
+        options
+    }
+
+    /// Resolves `baseUrl` against `config_dir`, the directory of the configuration file that set it.
+    ///
+    /// Relative values are joined onto `config_dir` and `.` and `..` segments are folded away; absolute values
+    /// are returned as they are. For a configuration loaded through `extends`, an inherited option is relative
+    /// to the file that defined it, which [TsConfig::parse_file_with_provenance] reports.
+    ///
+    /// ## Example
+    /// ```
+    /// use std::path::{Path, PathBuf};
+    /// use tsconfig::TsConfig;
+    ///
+    /// let json = r#"{"compilerOptions": {"baseUrl": "./src", "outDir": "../build"}}"#;
+    /// let options = TsConfig::parse_str(json).unwrap().compiler_options.unwrap();
+    /// let config_dir = Path::new("/work/app");
+    ///
+    /// assert_eq!(options.resolved_base_url(config_dir), Some(PathBuf::from("/work/app/src")));
+    /// assert_eq!(options.resolved_out_dir(config_dir), Some(PathBuf::from("/work/build")));
+    /// assert_eq!(options.resolved_root_dir(config_dir), None);
+    /// ```
+    pub fn resolved_base_url(&self, config_dir: &Path) -> Option<PathBuf> {
+        resolve_option_path(config_dir, &self.base_url)
+    }
+
+    /// Resolves `outDir` against `config_dir`, like [CompilerOptions::resolved_base_url].
+    pub fn resolved_out_dir(&self, config_dir: &Path) -> Option<PathBuf> {
+        resolve_option_path(config_dir, &self.out_dir)
+    }
+
+    /// Resolves `rootDir` against `config_dir`, like [CompilerOptions::resolved_base_url].
+    pub fn resolved_root_dir(&self, config_dir: &Path) -> Option<PathBuf> {
+        resolve_option_path(config_dir, &self.root_dir)
+    }
+
+    /// Resolves `declarationDir` against `config_dir`, like [CompilerOptions::resolved_base_url].
+    pub fn resolved_declaration_dir(&self, config_dir: &Path) -> Option<PathBuf> {
+        resolve_option_path(config_dir, &self.declaration_dir)
+    }
+
+    /// Resolves every directory-valued option against `config_dir` in place.
+    ///
+    /// `baseUrl`, `outDir`, `rootDir`, `declarationDir`, `typeRoots` and `rootDirs` are rewritten as with
+    /// [CompilerOptions::resolved_base_url]. A leading `${configDir}` template, which [TsConfig::parse_file]
+    /// expands already but [TsConfig::parse_str] leaves alone, is taken to mean `config_dir`.
+    ///
+    /// ## Example
+    /// ```
+    /// use std::path::Path;
+    /// use tsconfig::TsConfig;
+    ///
+    /// let json = r#"{"compilerOptions": {"outDir": "${configDir}/dist", "typeRoots": ["./types", "../node_modules/@types"]}}"#;
+    /// let mut options = TsConfig::parse_str(json).unwrap().compiler_options.unwrap();
+    /// options.normalize_paths(Path::new("/work/app"));
+    ///
+    /// assert_eq!(options.out_dir.as_deref(), Some("/work/app/dist"));
+    /// assert_eq!(
+    ///     options.type_roots,
+    ///     Some(vec!["/work/app/types".to_string(), "/work/node_modules/@types".to_string()])
+    /// );
+    /// ```
+    pub fn normalize_paths(&mut self, config_dir: &Path) {
+        let normalize = |path: &mut String| {
+            let relative = match path.strip_prefix("${configDir}") {
+                Some(rest) => format!(".{}", rest),
+                None => path.clone(),
+            };
+            *path = path_segments(config_dir, &relative)
+                .iter()
+                .collect::<PathBuf>()
+                .to_string_lossy()
+                .into_owned();
+        };
+
+        let options = [
+            &mut self.base_url,
+            &mut self.out_dir,
+            &mut self.root_dir,
+            &mut self.declaration_dir,
+        ];
+        IntoIterator::into_iter(options)
+            .flatten()
+            .for_each(normalize);
+        let lists = [&mut self.type_roots, &mut self.root_dirs];
+        for list in IntoIterator::into_iter(lists).flatten() {
+            list.iter_mut().for_each(normalize);
+        }
+    }
+
+    /// Works out where `tsc` writes the `.tsbuildinfo` file for the configuration file at `config_path`.
+    ///
+    /// Returns `None` unless `incremental` or `composite` is set. An explicit `tsBuildInfoFile` is resolved against
+    /// the configuration's directory. Otherwise, as in TypeScript, the file is named after `outFile` when that is
+    /// set, or after the configuration file and placed in `outDir`. If `rootDir` is set too, the configuration
+    /// file's path relative to `rootDir` is kept under `outDir`, even when that climbs back out with `..`: a
+    /// configuration next to a `src` root directory puts the file next to the configuration. Without `outDir` it sits
+    /// next to the configuration file.
+    ///
+    /// ## Example
+    /// ```
+    /// use std::path::{Path, PathBuf};
+    /// use tsconfig::TsConfig;
+    ///
+    /// let json = r#"{"compilerOptions": {"composite": true, "outDir": "dist"}}"#;
+    /// let options = TsConfig::parse_str(json).unwrap().compiler_options.unwrap();
+    ///
+    /// assert_eq!(
+    ///     options.effective_build_info_path(Path::new("/work/app/tsconfig.build.json")),
+    ///     Some(PathBuf::from("/work/app/dist/tsconfig.build.tsbuildinfo"))
+    /// );
+    /// ```
+    pub fn effective_build_info_path(&self, config_path: &Path) -> Option<PathBuf> {
+        if !(self.incremental.unwrap_or(false) || self.composite.unwrap_or(false)) {
+            return None;
+        }
+        let config_dir = config_path.parent().unwrap_or_else(|| Path::new(""));
+        if let Some(path) = resolve_option_path(config_dir, &self.ts_build_info_file) {
+            return Some(path);
+        }
+
+        let stem = match resolve_option_path(config_dir, &self.out_file) {
+            Some(out_file) => out_file.with_extension(""),
+            None => {
+                let config_stem: PathBuf = path_segments(&config_path.with_extension(""), "")
+                    .iter()
+                    .collect();
+                match self.resolved_out_dir(config_dir) {
+                    Some(out_dir) => match self.resolved_root_dir(config_dir) {
+                        Some(root_dir) => {
+                            let relative = relative_path(&root_dir, &config_stem);
+                            path_segments(&out_dir.join(relative), "").iter().collect()
+                        }
+                        None => out_dir.join(config_stem.file_name()?),
+                    },
+                    None => config_stem,
+                }
+            }
+        };
+        let mut path = stem.into_os_string();
+        path.push(".tsbuildinfo");
+        Some(path.into())
+    }
+
+    /// Returns the libraries available to the program: the `lib` list when it is set, and otherwise the
+    /// defaults TypeScript picks for the target.
+    ///
+    /// Without a `lib` list, TypeScript loads the library for the target's edition along with `dom`,
+    /// `webworker.importscripts` and `scripthost`, plus `dom.iterable` from ES2015 and `dom.asynciterable`
+    /// from ES2018. When no target is set either, the default ES5 target is assumed. `noLib` disables all of
+    /// them.
+    ///
+    /// ## Example
+    /// ```
+    /// use tsconfig::{Lib, TsConfig};
+    ///
+    /// let json = r#"{"compilerOptions": {"target": "es2017"}}"#;
+    /// let libs = TsConfig::parse_str(json).unwrap().compiler_options.unwrap().effective_libs();
+    ///
+    /// assert_eq!(
+    ///     libs,
+    ///     vec![Lib::Es2017, Lib::Dom, Lib::WebWorkerImportScripts, Lib::ScriptHost, Lib::DomIterable]
+    /// );
+    /// ```
+    pub fn effective_libs(&self) -> Vec<Lib> {
+        if self.no_lib == Some(true) {
+            return Vec::new();
+        }
+        if let Some(lib) = &self.lib {
+            return lib.clone();
+        }
+
+        let year = match &self.target {
+            Some(target) => target.era().unwrap_or(u32::MAX),
+            None => 5,
+        };
+        let edition = match year {
+            0..=2014 => Lib::Es5,
+            2015 => Lib::Es2015,
+            2016 => Lib::Es2016,
+            2017 => Lib::Es2017,
+            2018 => Lib::Es2018,
+            2019 => Lib::Es2019,
+            2020 => Lib::Es2020,
+            2021 => Lib::Es2021,
+            2022 => Lib::Es2022,
+            2023 => Lib::Es2023,
+            _ => Lib::EsNext,
+        };
+
+        let mut libs = vec![
+            edition,
+            Lib::Dom,
+            Lib::WebWorkerImportScripts,
+            Lib::ScriptHost,
+        ];
+        if year >= 2015 {
+            libs.push(Lib::DomIterable);
+        }
+        if year >= 2018 {
+            libs.push(Lib::DomAsyncIterable);
+        }
+        libs
+    }
+
+    /// Removes repeated entries from `lib`, keeping the first occurrence of each library in its original place.
+    ///
+    /// Spellings that parse to the same [Lib], such as `ES2020` and `es2020`, count as repeats.
+    ///
+    /// ## Example
+    /// ```
+    /// use tsconfig::{Lib, TsConfig};
+    ///
+    /// let json = r#"{"compilerOptions": {"lib": ["dom", "es2020", "DOM", "ES2020", "webworker"]}}"#;
+    /// let mut options = TsConfig::parse_str(json).unwrap().compiler_options.unwrap();
+    /// options.dedup_libs();
+    ///
+    /// assert_eq!(options.lib, Some(vec![Lib::Dom, Lib::Es2020, Lib::WebWorker]));
+    /// ```
+    pub fn dedup_libs(&mut self) {
+        if let Some(lib) = &mut self.lib {
+            let mut seen = HashSet::new();
+            lib.retain(|l| seen.insert(l.clone()));
+        }
+    }
+
+    /// Works out which strict-mode checks are enabled, applying `strict` to the checks that are not set individually.
+    ///
+    /// ## Example
+    /// ```
+    /// use tsconfig::TsConfig;
+    ///
+    /// let json = r#"{"compilerOptions": {"strict": true, "strictPropertyInitialization": false}}"#;
+    /// let flags = TsConfig::parse_str(json).unwrap().compiler_options.unwrap().effective_strict_flags();
+    ///
+    /// assert!(flags.strict_null_checks);
+    /// assert!(!flags.strict_property_initialization);
+    /// ```
+    pub fn effective_strict_flags(&self) -> StrictFlags {
+        let strict = self.strict.unwrap_or(false);
+        StrictFlags {
+            no_implicit_any: self.no_implicit_any.unwrap_or(strict),
+            strict_null_checks: self.strict_null_checks.unwrap_or(strict),
+            strict_function_types: self.strict_function_types.unwrap_or(strict),
+            strict_bind_call_apply: self.strict_bind_call_apply.unwrap_or(strict),
+            strict_property_initialization: self.strict_property_initialization.unwrap_or(strict),
+            no_implicit_this: self.no_implicit_this.unwrap_or(strict),
+            use_unknown_in_catch_variables: self.use_unknown_in_catch_variables.unwrap_or(strict),
+            strict_builtin_iterator_return: self.strict_builtin_iterator_return.unwrap_or(strict),
+            always_strict: self.always_strict.unwrap_or(strict),
+        }
+    }
+
+    /// Layers these options over `base` for [TsConfig::merged_with]: each option set here wins, and options this
+    /// crate does not model are merged by name.
+    #[allow(deprecated)]
+    fn merged_with(self, base: CompilerOptions) -> CompilerOptions {
+        let mut extra = base.extra;
+        extra.extend(self.extra);
+        CompilerOptions {
+            allow_js: self.allow_js.or(base.allow_js),
+            check_js: self.check_js.or(base.check_js),
+            composite: self.composite.or(base.composite),
+            declaration: self.declaration.or(base.declaration),
+            declaration_map: self.declaration_map.or(base.declaration_map),
+            downlevel_iteration: self.downlevel_iteration.or(base.downlevel_iteration),
+            import_helpers: self.import_helpers.or(base.import_helpers),
+            incremental: self.incremental.or(base.incremental),
+            isolated_modules: self.isolated_modules.or(base.isolated_modules),
+            verbatim_module_syntax: self.verbatim_module_syntax.or(base.verbatim_module_syntax),
+            jsx: self.jsx.or(base.jsx),
+            lib: self.lib.or(base.lib),
+            module: self.module.or(base.module),
+            no_emit: self.no_emit.or(base.no_emit),
+            out_dir: self.out_dir.or(base.out_dir),
+            out_file: self.out_file.or(base.out_file),
+            remove_comments: self.remove_comments.or(base.remove_comments),
+            root_dir: self.root_dir.or(base.root_dir),
+            source_map: self.source_map.or(base.source_map),
+            target: self.target.or(base.target),
+            ts_build_info_file: self.ts_build_info_file.or(base.ts_build_info_file),
+            always_strict: self.always_strict.or(base.always_strict),
+            no_implicit_any: self.no_implicit_any.or(base.no_implicit_any),
+            no_implicit_this: self.no_implicit_this.or(base.no_implicit_this),
+            strict: self.strict.or(base.strict),
+            strict_bind_call_apply: self.strict_bind_call_apply.or(base.strict_bind_call_apply),
+            strict_function_types: self.strict_function_types.or(base.strict_function_types),
+            strict_null_checks: self.strict_null_checks.or(base.strict_null_checks),
+            strict_property_initialization: self
+                .strict_property_initialization
+                .or(base.strict_property_initialization),
+            use_unknown_in_catch_variables: self
+                .use_unknown_in_catch_variables
+                .or(base.use_unknown_in_catch_variables),
+            strict_builtin_iterator_return: self
+                .strict_builtin_iterator_return
+                .or(base.strict_builtin_iterator_return),
+            allow_synthetic_default_imports: self
+                .allow_synthetic_default_imports
+                .or(base.allow_synthetic_default_imports),
+            allow_umd_global_access: self
+                .allow_umd_global_access
+                .or(base.allow_umd_global_access),
+            base_url: self.base_url.or(base.base_url),
+            es_module_interop: self.es_module_interop.or(base.es_module_interop),
+            module_resolution: self.module_resolution.or(base.module_resolution),
+            module_detection: self.module_detection.or(base.module_detection),
+            paths: self.paths.or(base.paths),
+            preserve_symlinks: self.preserve_symlinks.or(base.preserve_symlinks),
+            allow_arbitrary_extensions: self
+                .allow_arbitrary_extensions
+                .or(base.allow_arbitrary_extensions),
+            allow_importing_ts_extensions: self
+                .allow_importing_ts_extensions
+                .or(base.allow_importing_ts_extensions),
+            custom_conditions: self.custom_conditions.or(base.custom_conditions),
+            module_suffixes: self.module_suffixes.or(base.module_suffixes),
+            resolve_package_json_exports: self
+                .resolve_package_json_exports
+                .or(base.resolve_package_json_exports),
+            resolve_package_json_imports: self
+                .resolve_package_json_imports
+                .or(base.resolve_package_json_imports),
+            root_dirs: self.root_dirs.or(base.root_dirs),
+            type_roots: self.type_roots.or(base.type_roots),
+            types: self.types.or(base.types),
+            inline_source_map: self.inline_source_map.or(base.inline_source_map),
+            inline_sources: self.inline_sources.or(base.inline_sources),
+            map_root: self.map_root.or(base.map_root),
+            source_root: self.source_root.or(base.source_root),
+            no_fallthrough_cases_in_switch: self
+                .no_fallthrough_cases_in_switch
+                .or(base.no_fallthrough_cases_in_switch),
+            no_implicit_returns: self.no_implicit_returns.or(base.no_implicit_returns),
+            no_property_access_from_index_signature: self
+                .no_property_access_from_index_signature
+                .or(base.no_property_access_from_index_signature),
+            no_unchecked_indexed_access: self
+                .no_unchecked_indexed_access
+                .or(base.no_unchecked_indexed_access),
+            no_unused_locals: self.no_unused_locals.or(base.no_unused_locals),
+            no_implicit_override: self.no_implicit_override.or(base.no_implicit_override),
+            exact_optional_property_types: self
+                .exact_optional_property_types
+                .or(base.exact_optional_property_types),
+            no_unchecked_side_effect_imports: self
+                .no_unchecked_side_effect_imports
+                .or(base.no_unchecked_side_effect_imports),
+            emit_decorator_metadata: self
+                .emit_decorator_metadata
+                .or(base.emit_decorator_metadata),
+            experimental_decorators: self
+                .experimental_decorators
+                .or(base.experimental_decorators),
+            allow_unreachable_code: self.allow_unreachable_code.or(base.allow_unreachable_code),
+            allow_unused_labels: self.allow_unused_labels.or(base.allow_unused_labels),
+            assume_changes_only_affect_direct_dependencies: self
+                .assume_changes_only_affect_direct_dependencies
+                .or(base.assume_changes_only_affect_direct_dependencies),
+            charset: self.charset.or(base.charset),
+            declaration_dir: self.declaration_dir.or(base.declaration_dir),
+            diagnostics: self.diagnostics.or(base.diagnostics),
+            disable_referenced_project_load: self
+                .disable_referenced_project_load
+                .or(base.disable_referenced_project_load),
+            disable_size_limit: self.disable_size_limit.or(base.disable_size_limit),
+            disable_solution_searching: self
+                .disable_solution_searching
+                .or(base.disable_solution_searching),
+            disable_source_of_project_reference_redirect: self
+                .disable_source_of_project_reference_redirect
+                .or(base.disable_source_of_project_reference_redirect),
+            emit_bom: self.emit_bom.or(base.emit_bom),
+            emit_declaration_only: self.emit_declaration_only.or(base.emit_declaration_only),
+            explain_files: self.explain_files.or(base.explain_files),
+            extended_diagnostics: self.extended_diagnostics.or(base.extended_diagnostics),
+            force_consistent_casing_in_file_names: self
+                .force_consistent_casing_in_file_names
+                .or(base.force_consistent_casing_in_file_names),
+            generate_cpu_profile: self.generate_cpu_profile.or(base.generate_cpu_profile),
+            imports_not_used_as_values: self
+                .imports_not_used_as_values
+                .or(base.imports_not_used_as_values),
+            jsx_factory: self.jsx_factory.or(base.jsx_factory),
+            jsx_fragment_factory: self.jsx_fragment_factory.or(base.jsx_fragment_factory),
+            jsx_import_source: self.jsx_import_source.or(base.jsx_import_source),
+            keyof_strings_only: self.keyof_strings_only.or(base.keyof_strings_only),
+            list_emitted_files: self.list_emitted_files.or(base.list_emitted_files),
+            list_files: self.list_files.or(base.list_files),
+            max_node_module_js_depth: self
+                .max_node_module_js_depth
+                .or(base.max_node_module_js_depth),
+            new_line: self.new_line.or(base.new_line),
+            no_emit_helpers: self.no_emit_helpers.or(base.no_emit_helpers),
+            no_emit_on_error: self.no_emit_on_error.or(base.no_emit_on_error),
+            no_error_truncation: self.no_error_truncation.or(base.no_error_truncation),
+            no_implicit_use_strict: self.no_implicit_use_strict.or(base.no_implicit_use_strict),
+            no_lib: self.no_lib.or(base.no_lib),
+            no_resolve: self.no_resolve.or(base.no_resolve),
+            no_strict_generic_checks: self
+                .no_strict_generic_checks
+                .or(base.no_strict_generic_checks),
+            out: self.out.or(base.out),
+            preserve_const_enums: self.preserve_const_enums.or(base.preserve_const_enums),
+            react_namespace: self.react_namespace.or(base.react_namespace),
+            resolve_json_module: self.resolve_json_module.or(base.resolve_json_module),
+            skip_default_lib_check: self.skip_default_lib_check.or(base.skip_default_lib_check),
+            skip_lib_check: self.skip_lib_check.or(base.skip_lib_check),
+            strip_internal: self.strip_internal.or(base.strip_internal),
+            suppress_excess_property_errors: self
+                .suppress_excess_property_errors
+                .or(base.suppress_excess_property_errors),
+            suppress_implicit_any_index_errors: self
+                .suppress_implicit_any_index_errors
+                .or(base.suppress_implicit_any_index_errors),
+            trace_resolution: self.trace_resolution.or(base.trace_resolution),
+            use_define_for_class_fields: self
+                .use_define_for_class_fields
+                .or(base.use_define_for_class_fields),
+            preserve_watch_output: self.preserve_watch_output.or(base.preserve_watch_output),
+            plugins: self.plugins.or(base.plugins),
+            pretty: self.pretty.or(base.pretty),
+            fallback_polling: self.fallback_polling.or(base.fallback_polling),
+            watch_directory: self.watch_directory.or(base.watch_directory),
+            watch_file: self.watch_file.or(base.watch_file),
+            extra,
+        }
+    }
+
+    /// Converts the options that are set into a JSON object, keyed by their names in the file.
+    ///
+    /// This is the `compilerOptions` object that would be written back to a file: unset options are left out, names
+    /// are in camelCase, and options this crate does not model are included.
+    ///
+    /// ## Example
+    /// ```
+    /// use serde_json::Value;
+    /// use tsconfig::TsConfig;
+    ///
+    /// let json = r#"{"compilerOptions": {"noImplicitAny": true, "outDir": "dist"}}"#;
+    /// let options = TsConfig::parse_str(json).unwrap().compiler_options.unwrap();
+    /// let map = options.to_json_map();
+    ///
+    /// assert_eq!(map.len(), 2);
+    /// assert_eq!(map["noImplicitAny"], Value::Bool(true));
+    /// assert_eq!(map["outDir"], Value::from("dist"));
+    /// ```
+    pub fn to_json_map(&self) -> serde_json::Map<String, Value> {
+        match serde_json::to_value(self) {
+            Ok(Value::Object(options)) => options,
+            _ => serde_json::Map::new(),
+        }
+    }
+
+    /// Iterates over every option that is set, as its name in the file and its JSON value.
+    ///
+    /// The pairs are taken from [CompilerOptions::to_json_map], so they always agree with what would be written
+    /// back to a file. Options this crate does not model are included.
+    ///
+    /// ## Example
+    /// ```
+    /// use serde_json::Value;
+    /// use tsconfig::TsConfig;
+    ///
+    /// let json = r#"{"compilerOptions": {"strict": true, "target": "es2020", "futureFlag": 1}}"#;
+    /// let options = TsConfig::parse_str(json).unwrap().compiler_options.unwrap();
+    /// let mut set: Vec<(String, Value)> = options.set_options().collect();
+    /// set.sort_by(|a, b| a.0.cmp(&b.0));
+    ///
+    /// assert_eq!(
+    ///     set,
+    ///     vec![
+    ///         ("futureFlag".to_string(), Value::from(1)),
+    ///         ("strict".to_string(), Value::Bool(true)),
+    ///         ("target".to_string(), Value::from("es2020")),
+    ///     ]
+    /// );
+    /// ```
+    pub fn set_options(&self) -> impl Iterator<Item = (String, Value)> {
+        self.to_json_map().into_iter()
+    }
+
+    /// Lists the options whose values differ from `base`, as the option's name with its value in `base` and here.
+    ///
+    /// `None` stands for an option that is not set. Options are compared by their JSON values, as produced by
+    /// [CompilerOptions::set_options], and listed in alphabetical order.
+    ///
+    /// ## Example
+    /// ```
+    /// use serde_json::Value;
+    /// use tsconfig::TsConfig;
+    ///
+    /// let base = r#"{"compilerOptions": {"strict": true, "jsx": "react"}}"#;
+    /// let base = TsConfig::parse_str(base).unwrap().compiler_options.unwrap();
+    /// let project = r#"{"compilerOptions": {"strict": false, "jsx": "react", "outDir": "dist"}}"#;
+    /// let project = TsConfig::parse_str(project).unwrap().compiler_options.unwrap();
+    ///
+    /// assert_eq!(
+    ///     project.diff(&base),
+    ///     vec![
+    ///         ("outDir".to_string(), None, Some(Value::from("dist"))),
+    ///         ("strict".to_string(), Some(Value::Bool(true)), Some(Value::Bool(false))),
+    ///     ]
+    /// );
+    /// ```
+    pub fn diff(&self, base: &CompilerOptions) -> Vec<(String, Option<Value>, Option<Value>)> {
+        let mut old: HashMap<String, Value> = base.set_options().collect();
+        let mut changes: Vec<_> = self
+            .set_options()
+            .filter_map(|(option, new)| match old.remove(&option) {
+                Some(old) if old == new => None,
+                old => Some((option, old, Some(new))),
+            })
+            .collect();
+        changes.extend(
+            old.into_iter()
+                .map(|(option, old)| (option, Some(old), None)),
+        );
+        changes.sort_by(|a, b| a.0.cmp(&b.0));
+        changes
+    }
+
+    /// Lists the file extensions TypeScript picks up when expanding `include`, given these options.
+    ///
+    /// TypeScript files are always included. JavaScript files are included when `allowJs` is set, which defaults to
+    /// the value of `checkJs`, and `.json` files when `resolveJsonModule` is set. `allowArbitraryExtensions` only
+    /// affects how imports are resolved, since the declaration files it allows, such as `styles.d.css.ts`,
+    /// already end in `.ts`.
+    ///
+    /// ## Example
+    /// ```
+    /// use tsconfig::TsConfig;
+    ///
+    /// let json = r#"{"compilerOptions": {"checkJs": true, "resolveJsonModule": true}}"#;
+    /// let options = TsConfig::parse_str(json).unwrap().compiler_options.unwrap();
+    /// assert_eq!(
+    ///     options.included_extensions(),
+    ///     vec![".ts", ".tsx", ".mts", ".cts", ".js", ".jsx", ".mjs", ".cjs", ".json"]
+    /// );
+    /// ```
+    pub fn included_extensions(&self) -> Vec<&'static str> {
+        let mut extensions = vec![".ts", ".tsx", ".mts", ".cts"];
+        if self.allow_js.or(self.check_js).unwrap_or(false) {
+            extensions.extend([".js", ".jsx", ".mjs", ".cjs"]);
+        }
+        if self.resolve_json_module.unwrap_or(false) {
+            extensions.push(".json");
+        }
+        extensions
+    }
+
+    /// Works out the module the automatic JSX runtime is imported from, as `<source>/jsx-runtime`.
+    ///
+    /// Only the `react-jsx` and `react-jsxdev` modes use the automatic runtime, so this is `None` for any other
+    /// `jsx` setting. With those modes `jsxImportSource` defaults to `react`, and `jsxFactory` and
+    /// `jsxFragmentFactory` are ignored.
+    ///
+    /// ## Example
+    /// ```
+    /// use tsconfig::TsConfig;
+    ///
+    /// let json = r#"{"compilerOptions": {"jsx": "react-jsx", "jsxFactory": "h"}}"#;
+    /// let options = TsConfig::parse_str(json).unwrap().compiler_options.unwrap();
+    /// assert_eq!(options.effective_jsx_import_source().as_deref(), Some("react"));
+    ///
+    /// let json = r#"{"compilerOptions": {"jsx": "react-jsxdev", "jsxImportSource": "preact"}}"#;
+    /// let options = TsConfig::parse_str(json).unwrap().compiler_options.unwrap();
+    /// assert_eq!(options.effective_jsx_import_source().as_deref(), Some("preact"));
+    /// ```
+    pub fn effective_jsx_import_source(&self) -> Option<String> {
+        match self.jsx {
+            Some(Jsx::ReactJsx) | Some(Jsx::ReactJsxdev) => Some(
+                self.jsx_import_source
+                    .clone()
+                    .unwrap_or_else(|| "react".to_string()),
+            ),
+            _ => None,
+        }
+    }
+
+    /// Whether `paths` is set without `baseUrl`, which TypeScript versions before 4.1 reject.
+    ///
+    /// Newer versions accept this and resolve the mappings relative to the configuration file, as
+    /// [CompilerOptions::resolve_path_mapping] does, so this only matters when targeting an older compiler.
+    ///
+    /// ## Example
+    /// ```
+    /// use tsconfig::TsConfig;
+    ///
+    /// let json = r#"{"compilerOptions": {"paths": {"@app/*": ["./src/*"]}}}"#;
+    /// let options = TsConfig::parse_str(json).unwrap().compiler_options.unwrap();
+    /// assert!(options.path_mappings_require_base_url());
+    /// ```
+    pub fn path_mappings_require_base_url(&self) -> bool {
+        self.paths.is_some() && self.base_url.is_none()
+    }
+
+    /// Matches an import specifier against the `paths` mappings and returns the candidate locations it maps to.
+    ///
+    /// Patterns may contain a single `*`, which matches any text and is substituted into each candidate. An exact
+    /// pattern wins over a wildcard one, and among wildcard patterns the one with the longest prefix before the `*`
+    /// is used, as in TypeScript. Candidates are joined onto `baseUrl` when it is set, and otherwise returned as
+    /// written, relative to the configuration file. Returns `None` when no pattern matches.
+    ///
+    /// ## Example
+    /// ```
+    /// use tsconfig::TsConfig;
+    ///
+    /// let json = r#"{"compilerOptions": {"baseUrl": "src", "paths": {"@app/*": ["app/*", "generated/*.gen"]}}}"#;
+    /// let options = TsConfig::parse_str(json).unwrap().compiler_options.unwrap();
+    ///
+    /// assert_eq!(
+    ///     options.resolve_path_mapping("@app/models/user"),
+    ///     Some(vec!["src/app/models/user".to_string(), "src/generated/models/user.gen".to_string()])
+    /// );
+    /// assert_eq!(options.resolve_path_mapping("lodash"), None);
+    /// ```
+    pub fn resolve_path_mapping(&self, specifier: &str) -> Option<Vec<String>> {
+        let paths = self.paths.as_ref()?;
+
+        let (substitutions, matched) = match paths.get(specifier) {
+            Some(substitutions) => (substitutions, ""),
+            None => paths
+                .iter()
+                .filter_map(|(pattern, substitutions)| {
+                    let (prefix, suffix) = split_wildcard(pattern)?;
+                    if specifier.len() < prefix.len() + suffix.len()
+                        || !specifier.starts_with(prefix)
+                        || !specifier.ends_with(suffix)
+                    {
+                        return None;
+                    }
+                    let matched = &specifier[prefix.len()..specifier.len() - suffix.len()];
+                    Some((prefix.len(), pattern, substitutions, matched))
+                })
+                // Ties between equally long prefixes are broken by pattern so that the result does not depend on
+                // the iteration order of the map.
+                .max_by(|a, b| a.0.cmp(&b.0).then_with(|| b.1.cmp(a.1)))
+                .map(|(_, _, substitutions, matched)| (substitutions, matched))?,
+        };
+
+        let candidates = substitutions
+            .iter()
+            .map(|substitution| {
+                let path = substitution.replacen('*', matched, 1);
+                match self.base_url.as_deref() {
+                    Some(base_url) if !base_url.is_empty() => format!(
+                        "{}/{}",
+                        base_url.trim_end_matches('/'),
+                        path.trim_start_matches("./")
+                    ),
+                    _ => path,
+                }
+            })
+            .collect();
+        Some(candidates)
+    }
+}
+
+/// The path from the directory `from` to `to`, worked out lexically, climbing out of `from` with `..` as needed.
+fn relative_path(from: &Path, to: &Path) -> PathBuf {
+    let from: Vec<Component> = from.components().collect();
+    let to: Vec<Component> = to.components().collect();
+    let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+
+    let mut path: PathBuf = from[common..]
+        .iter()
+        .map(|_| Component::ParentDir)
+        .collect();
+    path.extend(&to[common..]);
+    path
+}
+
+fn resolve_option_path(config_dir: &Path, option: &Option<String>) -> Option<PathBuf> {
+    option
+        .as_ref()
+        .map(|path| path_segments(config_dir, path).iter().collect())
+}
+
+/// The strict-mode checks in effect for a set of compiler options, as computed by
+/// [CompilerOptions::effective_strict_flags].
+///
+/// Each check is enabled by `strict` unless the option for it is set explicitly, in which case that setting wins.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct StrictFlags {
+    pub no_implicit_any: bool,
+    pub strict_null_checks: bool,
+    pub strict_function_types: bool,
+    pub strict_bind_call_apply: bool,
+    pub strict_property_initialization: bool,
+    pub no_implicit_this: bool,
+    pub use_unknown_in_catch_variables: bool,
+    pub strict_builtin_iterator_return: bool,
+    pub always_strict: bool,
+}
+
+/// Splits a `paths` pattern around its wildcard, or returns `None` if it has no wildcard or more than one.
+fn split_wildcard(pattern: &str) -> Option<(&str, &str)> {
+    let star = pattern.find('*')?;
+    let (prefix, suffix) = (&pattern[..star], &pattern[star + 1..]);
+    if suffix.contains('*') {
+        return None;
+    }
+    Some((prefix, suffix))
+}
+
+/// A language service plugin, configured through the `plugins` compiler option.
+///
+/// ## Example
+/// ```
+/// use serde_json::Value;
+/// use tsconfig::TsConfig;
+///
+/// let json = r#"{"compilerOptions": {"plugins": [{"name": "typescript-plugin-css-modules", "classnameTransform": "camelCase"}]}}"#;
+/// let plugins = TsConfig::parse_str(json).unwrap().compiler_options.unwrap().plugins.unwrap();
+///
+/// assert_eq!(plugins[0].name, "typescript-plugin-css-modules");
+/// assert_eq!(plugins[0].options["classnameTransform"], Value::from("camelCase"));
+/// ```
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct Plugin {
+    /// The package name of the plugin.
+    pub name: String,
+    /// Every other setting in the plugin's entry, passed to the plugin as is.
+    #[serde(flatten)]
+    pub options: HashMap<String, Value>,
+}
+
+/// Module resolution mode
+///
+/// Specify the module resolution strategy: 'node' (Node.js) or 'classic' (used in TypeScript before the release of 1.6). You probably won’t need to use classic in modern code.
+/// There is a handbook reference page [on Module Resolution](https://www.typescriptlang.org/docs/handbook/module-resolution.html).
+#[derive(Debug, PartialEq, Clone, Eq, Hash)]
+pub enum ModuleResolutionMode {
+    Node,
+    Classic,
+    /// The name TypeScript 5.0 gives to the original `node` strategy.
+    Node10,
+    /// Node.js' resolution for ES modules and CommonJS, used with `module: node16`.
+    Node16,
+    /// Tracks the latest Node.js module resolution, used with `module: nodenext`.
+    NodeNext,
+    /// Resolution for code consumed by a bundler, which supports `exports` but never requires file extensions.
+    Bundler,
+    Other(String),
+}
+
+impl FromStr for ModuleResolutionMode {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let upper = s.to_uppercase();
+
+        let r = match upper.as_str() {
+            "NODE" => ModuleResolutionMode::Node,
+            "CLASSIC" => ModuleResolutionMode::Classic,
+            "NODE10" => ModuleResolutionMode::Node10,
+            "NODE16" => ModuleResolutionMode::Node16,
+            "NODENEXT" => ModuleResolutionMode::NodeNext,
+            "BUNDLER" => ModuleResolutionMode::Bundler,
+            _ => ModuleResolutionMode::Other(s.to_string()),
+        };
+
+        Ok(r)
+    }
+}
+
+impl<'de> Deserialize<'de> for ModuleResolutionMode {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        ModuleResolutionMode::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+impl ModuleResolutionMode {
+    /// The name TypeScript uses for this strategy, as written in a tsconfig.json or passed to `tsc --moduleResolution`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            ModuleResolutionMode::Node => "node",
+            ModuleResolutionMode::Classic => "classic",
+            ModuleResolutionMode::Node10 => "node10",
+            ModuleResolutionMode::Node16 => "node16",
+            ModuleResolutionMode::NodeNext => "nodenext",
+            ModuleResolutionMode::Bundler => "bundler",
+            ModuleResolutionMode::Other(other) => other,
+        }
+    }
+}
+
+impl std::fmt::Display for ModuleResolutionMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for ModuleResolutionMode {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// Controls how TypeScript decides whether a file is a script or a module.
+#[derive(Deserialize, Serialize, Debug, PartialEq, Copy, Clone, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum ModuleDetection {
+    /// Treat files with imports, exports, `import.meta`, JSX under `react-jsx`, or a module `type` in
+    /// package.json as modules
+    Auto,
+    /// Treat only files with imports or exports as modules, as before TypeScript 4.7
+    Legacy,
+    /// Treat every non-declaration file as a module
+    Force,
+}
+
+/// Controls what happens to imports that are only used for their types, through `importsNotUsedAsValues`.
+#[derive(Debug, PartialEq, Clone, Eq, Hash)]
+pub enum ImportsNotUsedAsValues {
+    /// Drop import statements that only reference types
+    Remove,
+    /// Keep every import statement, so that its side effects still run
+    Preserve,
+    /// Keep every import statement, and report an error for value imports that are only used as types
+    Error,
+    Other(String),
+}
+
+impl FromStr for ImportsNotUsedAsValues {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let upper = s.to_uppercase();
+
+        let r = match upper.as_str() {
+            "REMOVE" => ImportsNotUsedAsValues::Remove,
+            "PRESERVE" => ImportsNotUsedAsValues::Preserve,
+            "ERROR" => ImportsNotUsedAsValues::Error,
+            _ => ImportsNotUsedAsValues::Other(s.to_string()),
+        };
+
+        Ok(r)
+    }
+}
+
+impl<'de> Deserialize<'de> for ImportsNotUsedAsValues {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        ImportsNotUsedAsValues::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+impl ImportsNotUsedAsValues {
+    /// The name TypeScript uses for this setting, as written in a tsconfig.json or passed to `tsc --importsNotUsedAsValues`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            ImportsNotUsedAsValues::Remove => "remove",
+            ImportsNotUsedAsValues::Preserve => "preserve",
+            ImportsNotUsedAsValues::Error => "error",
+            ImportsNotUsedAsValues::Other(other) => other,
+        }
+    }
+}
+
+impl std::fmt::Display for ImportsNotUsedAsValues {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for ImportsNotUsedAsValues {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// Controls how JSX constructs are emitted in JavaScript files. This only affects output of JS files that started in .tsx files.
+///
+///
+/// For example, this sample code:
+///
+/// ```tsx
+/// export const helloWorld = () => <h1>Hello world</h1>;
+/// ```
+///
+/// Default: "react"
+///
+/// ```tsx
+/// export const helloWorld = () => React.createElement("h1", null, "Hello world");
+/// ```
+///
+/// Preserve: "preserve"
+///
+/// ```tsx
+/// export const helloWorld = () => <h1>Hello world</h1>;
+/// ```
+///
+/// React Native: "react-native"
+///
+/// ```tsx
+/// export const helloWorld = () => <h1>Hello world</h1>;
+/// ```
+///
+/// React 17 transform: "react-jsx"
+///
+/// ```tsx
+/// import { jsx as _jsx } from "react/jsx-runtime";
+/// export const helloWorld = () => _jsx("h1", { children: "Hello world" }, void 0);
+/// ```
+///
+/// React 17 dev transform: "react-jsxdev"
+///
+/// ```tsx
+/// import { jsxDEV as _jsxDEV } from "react/jsx-dev-runtime";
+/// const _jsxFileName = "/home/runner/work/TypeScript-Website/TypeScript-Website/packages/typescriptlang-org/index.tsx";
+/// export const helloWorld = () => _jsxDEV("h1", { children: "Hello world" }, void 0, false, { fileName: _jsxFileName, lineNumber: 7, columnNumber: 32 }, this);
+/// ```
+#[derive(Debug, PartialEq, Clone, Eq, Hash)]
+pub enum Jsx {
+    /// Emit .js files with JSX changed to the equivalent React.createElement calls
+    React,
+    /// Emit .js files with the JSX changed to _jsx calls
+    ReactJsx,
+    /// Emit .js files with the JSX to _jsx calls
+    ReactJsxdev,
+    /// Emit .js files with the JSX unchanged
+    ReactNative,
+    /// Emit .jsx files with the JSX unchanged
+    Preserve,
+    Other(String),
+}
+
+impl FromStr for Jsx {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let upper = s.to_uppercase();
+
+        let r = match upper.as_str() {
+            "REACT" => Jsx::React,
+            "REACT-JSX" => Jsx::ReactJsx,
+            "REACT-JSXDEV" => Jsx::ReactJsxdev,
+            "REACT-NATIVE" => Jsx::ReactNative,
+            "PRESERVE" => Jsx::Preserve,
+            _ => Jsx::Other(s.to_string()),
+        };
+
+        Ok(r)
+    }
+}
+
+impl<'de> Deserialize<'de> for Jsx {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Jsx::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+impl Jsx {
+    /// The name TypeScript uses for this mode, as written in a tsconfig.json or passed to `tsc --jsx`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Jsx::React => "react",
+            Jsx::ReactJsx => "react-jsx",
+            Jsx::ReactJsxdev => "react-jsxdev",
+            Jsx::ReactNative => "react-native",
+            Jsx::Preserve => "preserve",
+            Jsx::Other(other) => other,
+        }
+    }
+}
+
+impl std::fmt::Display for Jsx {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for Jsx {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// The line ending used in emitted files, set by `newLine`. The value is matched case-insensitively.
+///
+/// ## Example
+/// ```
+/// use tsconfig::{NewLine, TsConfig};
+///
+/// let json = r#"{"compilerOptions": {"newLine": "CRLF"}}"#;
+/// let options = TsConfig::parse_str(json).unwrap().compiler_options.unwrap();
+/// assert_eq!(options.new_line, Some(NewLine::Crlf));
+/// ```
+#[derive(Serialize, Debug, PartialEq, Copy, Clone, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum NewLine {
+    /// Unix line endings, `\n`
+    Lf,
+    /// Windows line endings, `\r\n`
+    Crlf,
+}
+
+impl<'de> Deserialize<'de> for NewLine {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        match s.to_lowercase().as_str() {
+            "lf" => Ok(NewLine::Lf),
+            "crlf" => Ok(NewLine::Crlf),
+            _ => Err(serde::de::Error::unknown_variant(&s, &["lf", "crlf"])),
+        }
+    }
+}
+
+/// The transpilation target for the emitted JavaScript.
+///
+/// Modern browsers support all `ES6` features, so `ES6` is a good choice. You might choose to set a lower target if your code
+/// is deployed to older environments, or a higher target if your code is guaranteed to run in newer environments.
+///
+/// The target setting changes which JS features are downleveled and which are left intact. For example, an arrow
+/// function () => this will be turned into an equivalent function expression if target is `ES5` or lower.
+///
+/// Changing target also changes the default value of lib. You may “mix and match” target and lib settings
+/// as desired, but you could just set target for convenience.
+///
+/// For developer platforms like Node will have a certain baselines for the their target depending on their version.
+/// You can find a set of community organized TSConfigs at tsconfig/bases for common platforms and their versions.
+///
+/// The special `ESNext` value refers to the highest version your version of TypeScript supports. This setting should be
+/// used with caution, since it doesn’t mean the same thing between different TypeScript versions and can
+/// make upgrades less predictable.
+#[derive(Debug, PartialEq, Clone, Eq, Hash)]
+pub enum Target {
+    Es3,
+    Es5,
+    Es2015,
+    Es6,
+    Es2016,
+    Es7,
+    Es2017,
+    Es2018,
+    Es2019,
+    Es2020,
+    Es2021,
+    Es2022,
+    Es2023,
+    EsNext,
+    Other(String),
+}
+
+/// Reads a `target` or `module` name, also accepting a bare edition number such as `2015`,
+/// which some generated configuration files use instead of `"es2015"`.
+///
+/// `option` names the option being read in the error for a value of the wrong type.
+fn deserialize_era<'de, D>(deserializer: D, option: &'static str) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct EraVisitor(&'static str);
+
+    impl serde::de::Visitor<'_> for EraVisitor {
+        type Value = String;
+
+        fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "a {} name or ECMAScript edition number", self.0)
+        }
+
+        fn visit_str<E>(self, name: &str) -> Result<String, E>
+        where
+            E: serde::de::Error,
+        {
+            Ok(name.to_string())
+        }
+
+        fn visit_u64<E>(self, edition: u64) -> Result<String, E>
+        where
+            E: serde::de::Error,
+        {
+            Ok(format!("ES{}", edition))
+        }
+    }
+
+    deserializer.deserialize_any(EraVisitor(option))
+}
+
+impl FromStr for Target {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let upper = s.to_uppercase();
+
+        let d = match upper.as_str() {
+            "ES3" => Target::Es3,
+            "ES5" => Target::Es5,
+            "ES2015" => Target::Es2015,
+            "ES6" => Target::Es6,
+            "ES2016" => Target::Es2016,
+            "ES7" => Target::Es7,
+            "ES2017" => Target::Es2017,
+            "ES2018" => Target::Es2018,
+            "ES2019" => Target::Es2019,
+            "ES2020" => Target::Es2020,
+            "ES2021" => Target::Es2021,
+            "ES2022" => Target::Es2022,
+            "ES2023" => Target::Es2023,
+            "ESNEXT" => Target::EsNext,
+            _ => Target::Other(s.to_string()),
+        };
+
+        Ok(d)
+    }
+}
+
+impl<'de> Deserialize<'de> for Target {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = deserialize_era(deserializer, "target")?;
+        Target::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+impl Target {
+    /// The name TypeScript uses for this target, as written in a tsconfig.json or passed to `tsc --target`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Target::Es3 => "es3",
+            Target::Es5 => "es5",
+            Target::Es2015 => "es2015",
+            Target::Es6 => "es6",
+            Target::Es2016 => "es2016",
+            Target::Es7 => "es7",
+            Target::Es2017 => "es2017",
+            Target::Es2018 => "es2018",
+            Target::Es2019 => "es2019",
+            Target::Es2020 => "es2020",
+            Target::Es2021 => "es2021",
+            Target::Es2022 => "es2022",
+            Target::Es2023 => "es2023",
+            Target::EsNext => "esnext",
+            Target::Other(other) => other,
+        }
+    }
+
+    /// The ECMAScript edition this target compiles to, as a year from ES2015 on.
+    ///
+    /// `es3` and `es5` are 3 and 5, aliases such as `es6` share the year of the edition they name, and `esnext`
+    /// sorts after every released edition. An unrecognised target has no edition.
+    ///
+    /// Targets are ordered by their eras, so `Target::Es6 >= Target::Es2015` holds.
+    ///
+    /// ## Example
+    /// ```
+    /// use tsconfig::Target;
+    ///
+    /// assert_eq!(Target::Es6.era(), Some(2015));
+    /// assert_eq!(Target::Es6.era(), Target::Es2015.era());
+    /// assert_eq!(Target::Other("es1999".to_string()).era(), None);
+    ///
+    /// assert!(Target::Es6 >= Target::Es2015);
+    /// assert!(Target::Es2020 >= Target::Es2018);
+    /// assert!(Target::Es5 < Target::Es2018);
+    /// ```
+    pub fn era(&self) -> Option<u32> {
+        let year = match self {
+            Target::Es3 => 3,
+            Target::Es5 => 5,
+            Target::Es2015 | Target::Es6 => 2015,
+            Target::Es2016 | Target::Es7 => 2016,
+            Target::Es2017 => 2017,
+            Target::Es2018 => 2018,
+            Target::Es2019 => 2019,
+            Target::Es2020 => 2020,
+            Target::Es2021 => 2021,
+            Target::Es2022 => 2022,
+            Target::Es2023 => 2023,
+            Target::EsNext => u32::MAX,
+            Target::Other(_) => return None,
+        };
+        Some(year)
+    }
+
+    /// Whether this target compiles to the edition of `other` or a later one, such as whether a configuration
+    /// targets at least ES2018.
+    ///
+    /// The same as `self >= other`, except that it returns `None` rather than `false` when the targets cannot be
+    /// compared because one of them is unrecognised. Aliases count as the same edition, so `es6` is at least
+    /// `es2015`.
+    ///
+    /// ## Example
+    /// ```
+    /// use tsconfig::Target;
+    ///
+    /// assert_eq!(Target::Es2020.at_least(&Target::Es2018), Some(true));
+    /// assert_eq!(Target::Es6.at_least(&Target::Es2015), Some(true));
+    /// assert_eq!(Target::Es5.at_least(&Target::Es2015), Some(false));
+    /// assert_eq!(Target::Other("es1999".to_string()).at_least(&Target::Es5), None);
+    /// ```
+    pub fn at_least(&self, other: &Target) -> Option<bool> {
+        self.partial_cmp(other)
+            .map(|ordering| ordering != Ordering::Less)
+    }
+}
+
+/// Orders targets by [Target::era], so that `>` and `<` compare the language versions they compile to.
+///
+/// Aliases of the same edition, such as `es6` and `es2015`, compare as [Ordering::Equal] and so satisfy `>=`
+/// checks, even though `==` still tells the two spellings apart. Unrecognised targets are only ordered against
+/// themselves.
+impl PartialOrd for Target {
+    fn partial_cmp(&self, other: &Target) -> Option<Ordering> {
+        if self == other {
+            return Some(Ordering::Equal);
+        }
+        Some(self.era()?.cmp(&other.era()?))
+    }
+}
+
+impl std::fmt::Display for Target {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for Target {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// Available definitions for built-in JS APIs.
+///
+/// TypeScript includes a default set of type definitions for built-in JS APIs (like Math), as well as type definitions for things found in browser environments (like document). TypeScript also includes APIs for newer JS features matching the target you specify; for example the definition for Map is available if target is ES6 or newer.
+///
+/// You may want to change these for a few reasons:
+///
+/// * Your program doesn't run in a browser, so you don’t want the "dom" type definitions
+/// * Your runtime platform provides certain JavaScript API objects (maybe through polyfills), but doesn't yet support the full syntax of a given ECMAScript version
+/// * You have polyfills or native implementations for some, but not all, of a higher level ECMAScript version
+///
+#[derive(Debug, PartialEq, Clone, Eq, Hash)]
+pub enum Lib {
+    Es5,
+    Es2015,
+    Es6,
+    Es2016,
+    Es7,
+    Es2017,
+    Es2018,
+    Es2019,
+    Es2020,
+    Es2021,
+    Es2022,
+    Es2023,
+    EsNext,
+    Dom,
+    WebWorker,
+    WebWorkerImportScripts,
+    ScriptHost,
+    DomIterable,
+    DomAsyncIterable,
+    Es2015Core,
+    Es2015Generator,
+    Es2015Iterable,
+    Es2015Promise,
+    Es2015Proxy,
+    Es2015Reflect,
+    Es2015Symbol,
+    Es2015SymbolWellKnown,
+    Es2016ArrayInclude,
+    Es2017Object,
+    Es2017Intl,
+    Es2017SharedMemory,
+    Es2017String,
+    Es2017TypedArrays,
+    Es2018Intl,
+    Es2018Promise,
+    Es2018RegExp,
+    Es2019Array,
+    Es2019Object,
+    Es2019String,
+    Es2019Symbol,
+    Es2020String,
+    Es2020SymbolWellknown,
+    EsNextAsyncIterable,
+    EsNextArray,
+    EsNextIntl,
+    EsNextSymbol,
+    EsNextDisposable,
+    Other(String),
+}
+
+impl FromStr for Lib {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let upper = s.to_uppercase();
+
+        let d = match upper.as_str() {
+            "ES5" => Lib::Es5,
+            "ES2015" => Lib::Es2015,
+            "ES6" => Lib::Es6,
+            "ES2016" => Lib::Es2016,
+            "ES7" => Lib::Es7,
+            "ES2017" => Lib::Es2017,
+            "ES2018" => Lib::Es2018,
+            "ES2019" => Lib::Es2019,
+            "ES2020" => Lib::Es2020,
+            "ES2021" => Lib::Es2021,
+            "ES2022" => Lib::Es2022,
+            "ES2023" => Lib::Es2023,
+            "ESNEXT" => Lib::EsNext,
+            "DOM" => Lib::Dom,
+            "WEBWORKER" => Lib::WebWorker,
+            "WEBWORKER.IMPORTSCRIPTS" => Lib::WebWorkerImportScripts,
+            "SCRIPTHOST" => Lib::ScriptHost,
+            "DOM.ITERABLE" => Lib::DomIterable,
+            "DOM.ASYNCITERABLE" => Lib::DomAsyncIterable,
+            "ES2015.CORE" => Lib::Es2015Core,
+            "ES2015.GENERATOR" => Lib::Es2015Generator,
+            "ES2015.ITERABLE" => Lib::Es2015Iterable,
+            "ES2015.PROMISE" => Lib::Es2015Promise,
+            "ES2015.PROXY" => Lib::Es2015Proxy,
+            "ES2015.REFLECT" => Lib::Es2015Reflect,
+            "ES2015.SYMBOL" => Lib::Es2015Symbol,
+            "ES2015.SYMBOL.WELLKNOWN" => Lib::Es2015SymbolWellKnown,
+            "ES2016.ARRAY.INCLUDE" => Lib::Es2016ArrayInclude,
+            "ES2017.OBJECT" => Lib::Es2017Object,
+            "ES2017.INTL" => Lib::Es2017Intl,
+            "ES2017.SHAREDMEMORY" => Lib::Es2017SharedMemory,
+            "ES2017.STRING" => Lib::Es2017String,
+            "ES2017.TYPEDARRAYS" => Lib::Es2017TypedArrays,
+            "ES2018.INTL" => Lib::Es2018Intl,
+            "ES2018.PROMISE" => Lib::Es2018Promise,
+            "ES2018.REGEXP" => Lib::Es2018RegExp,
+            "ES2019.ARRAY" => Lib::Es2019Array,
+            "ES2019.OBJECT" => Lib::Es2019Object,
+            "ES2019.STRING" => Lib::Es2019String,
+            "ES2019.SYMBOL" => Lib::Es2019Symbol,
+            "ES2020.STRING" => Lib::Es2020String,
+            "ES2020.SYMBOL.WELLKNOWN" => Lib::Es2020SymbolWellknown,
+            "ESNEXT.ASYNCITERABLE" => Lib::EsNextAsyncIterable,
+            "ESNEXT.ARRAY" => Lib::EsNextArray,
+            "ESNEXT.INTL" => Lib::EsNextIntl,
+            "ESNEXT.SYMBOL" => Lib::EsNextSymbol,
+            "ESNEXT.DISPOSABLE" => Lib::EsNextDisposable,
+            _ => Lib::Other(s.to_string()),
+        };
+
+        Ok(d)
+    }
+}
+
+impl<'de> Deserialize<'de> for Lib {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Lib::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+impl Lib {
+    /// The name TypeScript uses for this library, as written in a tsconfig.json or passed to `tsc --lib`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Lib::Es5 => "es5",
+            Lib::Es2015 => "es2015",
+            Lib::Es6 => "es6",
+            Lib::Es2016 => "es2016",
+            Lib::Es7 => "es7",
+            Lib::Es2017 => "es2017",
+            Lib::Es2018 => "es2018",
+            Lib::Es2019 => "es2019",
+            Lib::Es2020 => "es2020",
+            Lib::Es2021 => "es2021",
+            Lib::Es2022 => "es2022",
+            Lib::Es2023 => "es2023",
+            Lib::EsNext => "esnext",
+            Lib::Dom => "dom",
+            Lib::WebWorker => "webworker",
+            Lib::WebWorkerImportScripts => "webworker.importscripts",
+            Lib::ScriptHost => "scripthost",
+            Lib::DomIterable => "dom.iterable",
+            Lib::DomAsyncIterable => "dom.asynciterable",
+            Lib::Es2015Core => "es2015.core",
+            Lib::Es2015Generator => "es2015.generator",
+            Lib::Es2015Iterable => "es2015.iterable",
+            Lib::Es2015Promise => "es2015.promise",
+            Lib::Es2015Proxy => "es2015.proxy",
+            Lib::Es2015Reflect => "es2015.reflect",
+            Lib::Es2015Symbol => "es2015.symbol",
+            Lib::Es2015SymbolWellKnown => "es2015.symbol.wellknown",
+            Lib::Es2016ArrayInclude => "es2016.array.include",
+            Lib::Es2017Object => "es2017.object",
+            Lib::Es2017Intl => "es2017.intl",
+            Lib::Es2017SharedMemory => "es2017.sharedmemory",
+            Lib::Es2017String => "es2017.string",
+            Lib::Es2017TypedArrays => "es2017.typedarrays",
+            Lib::Es2018Intl => "es2018.intl",
+            Lib::Es2018Promise => "es2018.promise",
+            Lib::Es2018RegExp => "es2018.regexp",
+            Lib::Es2019Array => "es2019.array",
+            Lib::Es2019Object => "es2019.object",
+            Lib::Es2019String => "es2019.string",
+            Lib::Es2019Symbol => "es2019.symbol",
+            Lib::Es2020String => "es2020.string",
+            Lib::Es2020SymbolWellknown => "es2020.symbol.wellknown",
+            Lib::EsNextAsyncIterable => "esnext.asynciterable",
+            Lib::EsNextArray => "esnext.array",
+            Lib::EsNextIntl => "esnext.intl",
+            Lib::EsNextSymbol => "esnext.symbol",
+            Lib::EsNextDisposable => "esnext.disposable",
+            Lib::Other(other) => other,
+        }
+    }
+}
+
+impl std::fmt::Display for Lib {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for Lib {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// Sets the module system for the program.
+///
+/// See the [Modules reference page](https://www.typescriptlang.org/docs/handbook/modules.html)
+/// for more information. You very likely want "CommonJS" for node projects.
+///
+/// Changing module affects moduleResolution which also has a reference page.
+///
+/// Here’s some example output for this file:
+///
+/// ```tsx
+/// // @filename: index.ts
+/// import { valueOfPi } from "./constants";
+///
+///
+/// export const twoPi = valueOfPi * 2;
+/// ```
+///
+/// ## CommonJS
+///
+/// ```js
+/// "use strict";
+/// Object.defineProperty(exports, "__esModule", { value: true });
+/// exports.twoPi = void 0;
+/// const constants_1 = require("./constants");
+/// exports.twoPi = constants_1.valueOfPi * 2;
+/// ```
+///
+/// ## UMD
+///
+/// ```js
+/// (function (factory) {
+///     if (typeof module === "object" && typeof module.exports === "object") {
+///         var v = factory(require, exports);
+///         if (v !== undefined) module.exports = v;
+///     }
+///     else if (typeof define === "function" && define.amd) {
+///         define(["require", "exports", "./constants"], factory);
+///     }
+/// })(function (require, exports) {
+///     "use strict";
+///     Object.defineProperty(exports, "__esModule", { value: true });
+///     exports.twoPi = void 0;
+///     const constants_1 = require("./constants");
+///     exports.twoPi = constants_1.valueOfPi * 2;
+/// });
+/// ```
+///
+/// ## AMD
+///
+/// ```js
+/// define(["require", "exports", "./constants"], function (require, exports, constants_1) {
+///     "use strict";
+///     Object.defineProperty(exports, "__esModule", { value: true });
+///     exports.twoPi = void 0;
+///     exports.twoPi = constants_1.valueOfPi * 2;
+/// });
+/// ```
+///
+/// ## System
+///
+/// ```js
+/// System.register(["./constants"], function (exports_1, context_1) {
+///     "use strict";
+///     var constants_1, twoPi;
+///     var __moduleName = context_1 && context_1.id;
+///     return {
+///         setters: [
+///             function (constants_1_1) {
+///                 constants_1 = constants_1_1;
+///             }
+///         ],
+///         execute: function () {
+///             exports_1("twoPi", twoPi = constants_1.valueOfPi * 2);
+///         }
+///     };
+/// });
+/// ```
+///
+/// ## ESNext
+///
+/// ```js
+/// import { valueOfPi } from "./constants";
+/// export const twoPi = valueOfPi * 2;
+/// ```
+///
+/// ## ES2020
+///
+/// ```js
+/// import { valueOfPi } from "./constants";
+/// export const twoPi = valueOfPi * 2;
+/// ```
+///
+/// ## None
+///
+/// ```js
+/// "use strict";
+/// Object.defineProperty(exports, "__esModule", { value: true });
+/// exports.twoPi = void 0;
+/// const constants_1 = require("./constants");
+/// exports.twoPi = constants_1.valueOfPi * 2;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Module {
+    CommonJs,
+    Es6,
+    Es2015,
+    Es2020,
+    None,
+    Umd,
+    Amd,
+    System,
+    EsNext,
+    Node16,
+    Node18,
+    NodeNext,
+    Other(String),
+}
+
+impl FromStr for Module {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let upper = s.to_uppercase();
+
+        let r = match upper.as_str() {
+            "COMMONJS" => Module::CommonJs,
+            "ESNEXT" => Module::EsNext,
+            "ES6" => Module::Es6,
+            "ES2015" => Module::Es2015,
+            "ES2020" => Module::Es2020,
+            "NONE" => Module::None,
+            "UMD" => Module::Umd,
+            "AMD" => Module::Amd,
+            "SYSTEM" => Module::System,
+            "NODE16" => Module::Node16,
+            "NODE18" => Module::Node18,
+            "NODENEXT" => Module::NodeNext,
+            _ => Module::Other(s.to_string()),
+        };
+
+        Ok(r)
+    }
+}
+
+impl<'de> Deserialize<'de> for Module {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = deserialize_era(deserializer, "module")?;
+        Module::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+impl Module {
+    /// The name TypeScript uses for this module kind, as written in a tsconfig.json or passed to `tsc --module`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Module::CommonJs => "commonjs",
+            Module::Es6 => "es6",
+            Module::Es2015 => "es2015",
+            Module::Es2020 => "es2020",
+            Module::None => "none",
+            Module::Umd => "umd",
+            Module::Amd => "amd",
+            Module::System => "system",
+            Module::EsNext => "esnext",
+            Module::Node16 => "node16",
+            Module::Node18 => "node18",
+            Module::NodeNext => "nodenext",
+            Module::Other(other) => other,
+        }
+    }
+}
+
+impl std::fmt::Display for Module {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for Module {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[test]
+    fn parse_jsx() {
+        let json = r#"{"compilerOptions": {"jsx": "react-jsx"}}"#;
+
+        let config = TsConfig::parse_str(json).unwrap();
+        assert_eq!(config.compiler_options.unwrap().jsx, Some(Jsx::ReactJsx));
+    }
+
+    #[test]
+    fn parse_every_jsx_mode() {
+        for (name, jsx) in &[
+            ("react", Jsx::React),
+            ("react-jsx", Jsx::ReactJsx),
+            ("react-jsxdev", Jsx::ReactJsxdev),
+            ("react-native", Jsx::ReactNative),
+            ("preserve", Jsx::Preserve),
+        ] {
+            let json = format!(r#"{{"compilerOptions": {{"jsx": "{}"}}}}"#, name);
+            let options = TsConfig::parse_str(&json)
+                .unwrap()
+                .compiler_options
+                .unwrap();
+            assert_eq!(options.jsx.as_ref(), Some(jsx));
+            assert_eq!(serde_json::to_value(&options).unwrap()["jsx"], *name);
+        }
+
+        let json = r#"{"compilerOptions": {"jsx": "react-jsx-future", "strict": true}}"#;
+        let options = TsConfig::parse_str(json).unwrap().compiler_options.unwrap();
+        assert_eq!(
+            options.jsx,
+            Some(Jsx::Other("react-jsx-future".to_string()))
+        );
+        assert_eq!(options.strict, Some(true));
+    }
+
+    #[test]
+    fn parse_paths() {
+        let json = r#"{
+        "compilerOptions": {
+            "baseUrl": "src",
+            "paths": {
+                "tests/*": ["tests/*"],
+                "blah": ["bloop"]
+            }
+        }
+    }
+        
+        "#;
+
+        let config = TsConfig::parse_str(json).unwrap();
+        assert_eq!(
+            config
+                .compiler_options
+                .unwrap()
+                .paths
+                .unwrap()
+                .get("tests/*"),
+            Some(&vec!["tests/*".to_string()])
+        );
+    }
+
+    #[test]
+    fn parse_empty() {
+        TsConfig::parse_str("{}").unwrap();
+        TsConfig::parse_str(r#"{"compilerOptions": {}}"#).unwrap();
+    }
+
+    #[test]
+    fn parse_default() {
+        let json = include_str!("../test/tsconfig.default.json");
+        TsConfig::parse_str(json).unwrap();
+    }
+
+    #[test]
+    fn parse_common_tsconfig() {
+        let json = include_str!("../test/tsconfig.common.json");
+        TsConfig::parse_str(json).unwrap();
+    }
+
+    #[test]
+    fn parse_complete_tsconfig() {
+        let json = include_str!("../test/tsconfig.complete.json");
+        let config = TsConfig::parse_str(json).unwrap();
+
+        let options = config.compiler_options.unwrap();
+        assert_eq!(options.exact_optional_property_types, Some(true));
+        assert_eq!(options.no_unchecked_side_effect_imports, Some(true));
+        assert_eq!(options.no_implicit_override, Some(true));
+        assert_eq!(options.allow_arbitrary_extensions, Some(false));
+        assert_eq!(options.allow_importing_ts_extensions, Some(false));
+        assert!(options
+            .unknown_options()
+            .get("noImplicitOverride")
+            .is_none());
+    }
+
+    #[test]
+    fn ignores_invalid_fields() {
+        let json = r#"{"bleep": true, "compilerOptions": {"someNewUnsupportedProperty": false}}"#;
+        TsConfig::parse_str(json).unwrap();
+    }
+
+    #[test]
+    fn ignores_dangling_commas() {
+        let json = r#"{"compilerOptions": {"noImplicitAny": false,"explainFiles": true,}}"#;
+        let cfg = TsConfig::parse_str(json).unwrap();
+        assert!(cfg.compiler_options.unwrap().explain_files.unwrap());
+
+        let json = r#"{"compilerOptions": {"noImplicitAny": false,"explainFiles": true, }}"#;
+        let cfg = TsConfig::parse_str(json).unwrap();
+        assert!(cfg.compiler_options.unwrap().explain_files.unwrap());
+
+        let json = r#"{"compilerOptions": {"noImplicitAny": false,"explainFiles": true,
+    }}"#;
+        let cfg = TsConfig::parse_str(json).unwrap();
+        assert!(cfg.compiler_options.unwrap().explain_files.unwrap());
+
+        let json = r#"{"compilerOptions": {"explainFiles": true, // last option
+    /* nothing else */ }}"#;
+        let cfg = TsConfig::parse_str(json).unwrap();
+        assert!(cfg.compiler_options.unwrap().explain_files.unwrap());
+    }
+
+    #[test]
+    fn merge_two_configs() {
+        let json_1 = r#"{"compilerOptions": {"jsx": "react", "noEmit": true,}}"#;
+        let json_2 = r#"{"compilerOptions": {"jsx": "preserve", "removeComments": true}}"#;
+
+        let mut value1: Value = parse_to_value(json_1).unwrap();
+        let value2: Value = parse_to_value(json_2).unwrap();
+
+        merge(&mut value1, value2);
+
+        let value: TsConfig = serde_json::from_value(value1).unwrap();
+
+        assert_eq!(
+            value.clone().compiler_options.unwrap().jsx,
+            Some(Jsx::React)
+        );
+        assert_eq!(value.clone().compiler_options.unwrap().no_emit, Some(true));
+        assert_eq!(value.compiler_options.unwrap().remove_comments, Some(true));
+    }
+
+    #[test]
+    fn parse_basic_file() {
+        let path = Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap())
+            .join("test/tsconfig.default.json");
+        let config = TsConfig::parse_file(&path).unwrap();
+
+        assert_eq!(
+            config.compiler_options.clone().unwrap().target,
+            Some(Target::Es5)
+        );
+        assert_eq!(
+            config.compiler_options.clone().unwrap().module,
+            Some(Module::CommonJs)
+        );
+        assert_eq!(config.compiler_options.unwrap().strict, Some(true));
+    }
+
+    #[test]
+    fn parse_inheriting_file() {
+        let path = Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap())
+            .join("test/tsconfig.inherits.json");
+        let config = TsConfig::parse_file(&path).unwrap();
+
+        assert_eq!(
+            config
+                .compiler_options
+                .clone()
+                .unwrap()
+                .use_define_for_class_fields,
+            Some(false)
+        );
+
+        assert_eq!(
+            config.compiler_options.clone().unwrap().declaration,
+            Some(true)
+        );
+
+        assert_eq!(
+            config.compiler_options.unwrap().trace_resolution,
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn parse_inheritance_chain() {
+        let path = Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap())
+            .join("test/a/tsconfig.inherits_again.json");
+        let config = TsConfig::parse_file(&path).unwrap();
+
+        assert_eq!(
+            config
+                .compiler_options
+                .clone()
+                .unwrap()
+                .use_define_for_class_fields,
+            Some(false)
+        );
+
+        assert_eq!(
+            config.compiler_options.clone().unwrap().declaration,
+            Some(true)
+        );
+
+        assert_eq!(
+            config.compiler_options.clone().unwrap().trace_resolution,
+            Some(false)
+        );
+
+        assert_eq!(config.compiler_options.unwrap().jsx, Some(Jsx::ReactNative));
+    }
+
+    #[test]
+    fn serialize_enums_to_canonical_strings() {
+        assert_eq!(
+            serde_json::to_value(Target::Es2015).unwrap(),
+            Value::String("es2015".to_string())
+        );
+        assert_eq!(
+            serde_json::to_value(Module::CommonJs).unwrap(),
+            Value::String("commonjs".to_string())
+        );
+        assert_eq!(
+            serde_json::to_value(Lib::DomIterable).unwrap(),
+            Value::String("dom.iterable".to_string())
+        );
+        assert_eq!(
+            serde_json::to_value(Jsx::ReactJsxdev).unwrap(),
+            Value::String("react-jsxdev".to_string())
+        );
+    }
+
+    #[test]
+    fn serialize_skips_unset_options() {
+        let json = r#"{"include": ["src"], "compilerOptions": {"noEmit": true}}"#;
+        let config = TsConfig::parse_str(json).unwrap();
+
+        let value = serde_json::to_value(&config).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({"include": ["src"], "compilerOptions": {"noEmit": true}})
+        );
+    }
+
+    #[test]
+    fn round_trip_complete_tsconfig() {
+        let json = include_str!("../test/tsconfig.complete.json");
+        let config = TsConfig::parse_str(json).unwrap();
+        let serialized = serde_json::to_string(&config).unwrap();
+        let reparsed = TsConfig::parse_str(&serialized).unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&config).unwrap(),
+            serde_json::to_value(&reparsed).unwrap()
+        );
+        assert_eq!(
+            serde_json::to_value(&reparsed).unwrap()["compilerOptions"]["target"],
+            Value::String("esnext".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_extends_array() {
+        let json = r#"{"extends": ["./a.json", "./b.json"]}"#;
+        let config = TsConfig::parse_str(json).unwrap();
+        assert_eq!(
+            config.extends,
+            Some(Extends::Multiple(vec![
+                "./a.json".to_string(),
+                "./b.json".to_string()
+            ]))
+        );
+
+        let json = r#"{"extends": "./a.json"}"#;
+        let config = TsConfig::parse_str(json).unwrap();
+        assert_eq!(
+            config.extends,
+            Some(Extends::Single("./a.json".to_string()))
+        );
+    }
+
+    #[test]
+    fn parse_file_extending_multiple_files() {
+        let path = Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap())
+            .join("test/tsconfig.extends_multiple.json");
+        let config = TsConfig::parse_file(&path).unwrap();
+        let compiler_options = config.compiler_options.unwrap();
+
+        // Only set in the first base.
+        assert_eq!(compiler_options.use_define_for_class_fields, Some(false));
+        assert_eq!(compiler_options.trace_resolution, Some(true));
+        // Set in both bases, the later one wins.
+        assert_eq!(compiler_options.jsx, Some(Jsx::React));
+        // Only set in the second base.
+        assert_eq!(compiler_options.strict, Some(true));
+        // Set in the extending file itself.
+        assert_eq!(compiler_options.declaration, Some(true));
+    }
+
+    #[test]
+    fn resolve_extends_relative_path() {
+        let config_path = Path::new("project/tsconfig.json");
+        assert_eq!(
+            resolve_extends_path(&FsResolver, config_path, "./configs/base.json"),
+            Path::new("project/configs/base.json")
+        );
+        assert_eq!(
+            resolve_extends_path(&FsResolver, config_path, "../base.json"),
+            Path::new("project/../base.json")
+        );
+        assert_eq!(
+            resolve_extends_path(&FsResolver, config_path, "./configs/base"),
+            Path::new("project/configs/base.json")
+        );
+        assert_eq!(
+            resolve_extends_path(&FsResolver, config_path, "./tsconfig.base"),
+            Path::new("project/tsconfig.base.json")
+        );
+    }
+
+    #[test]
+    fn resolve_extends_from_node_modules() {
+        let test_dir = Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap()).join("test");
+        let config_path = test_dir.join("a/tsconfig.json");
+        let node_modules = test_dir.join("node_modules");
+
+        assert_eq!(
+            resolve_extends_path(&FsResolver, &config_path, "@tsconfig/strictest"),
+            node_modules.join("@tsconfig/strictest/tsconfig.json")
+        );
+        assert_eq!(
+            resolve_extends_path(
+                &FsResolver,
+                &config_path,
+                "@tsconfig/strictest/tsconfig.json"
+            ),
+            node_modules.join("@tsconfig/strictest/tsconfig.json")
+        );
+        assert_eq!(
+            resolve_extends_path(&FsResolver, &config_path, "@tsconfig/strictest/tsconfig"),
+            node_modules.join("@tsconfig/strictest/tsconfig.json")
+        );
+        assert_eq!(
+            resolve_extends_path(&FsResolver, &config_path, "exported-config"),
+            node_modules.join("exported-config/./configs/base.json")
+        );
+        assert_eq!(
+            resolve_extends_path(&FsResolver, &config_path, "exported-config/strict"),
+            node_modules.join("exported-config/./configs/strict.json")
+        );
+    }
+
+    #[test]
+    fn parse_file_extending_packages() {
+        let path = Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap())
+            .join("test/a/tsconfig.packages.json");
+        let config = TsConfig::parse_file(&path).unwrap();
+        let compiler_options = config.compiler_options.unwrap();
+
+        assert_eq!(compiler_options.strict, Some(true));
+        assert_eq!(compiler_options.no_unused_locals, Some(true));
+        assert_eq!(compiler_options.target, Some(Target::Es2020));
+        assert_eq!(compiler_options.module, Some(Module::CommonJs));
+    }
+
+    #[test]
+    fn parse_file_with_custom_resolver() {
+        struct FixtureResolver;
+
+        impl ConfigResolver for FixtureResolver {
+            fn resolve_extends(&self, config_path: &Path, specifier: &str) -> Result<PathBuf> {
+                let file = match specifier {
+                    "exported-config" => "../b/tsconfig.base.json",
+                    "@tsconfig/strictest" => "../b/tsconfig.strict.json",
+                    other => panic!("unexpected specifier {}", other),
+                };
+                Ok(config_path.parent().unwrap().join(file))
+            }
+        }
+
+        let path = Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap())
+            .join("test/a/tsconfig.packages.json");
+        let value = parse_file_to_value_with_resolver(&path, &FixtureResolver).unwrap();
+
+        assert_eq!(
+            value["compilerOptions"]["jsx"],
+            Value::String("react".to_string())
+        );
+        assert_eq!(
+            value["compilerOptions"]["useDefineForClassFields"],
+            Value::Bool(false)
+        );
+        assert_eq!(value["compilerOptions"]["noUnusedLocals"], Value::Null);
+    }
+
+    #[test]
+    fn detect_circular_extends() {
+        let dir = Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap()).join("test/circular");
+        let a = std::fs::canonicalize(dir.join("tsconfig.a.json")).unwrap();
+        let b = std::fs::canonicalize(dir.join("tsconfig.b.json")).unwrap();
+
+        match TsConfig::parse_file(&dir.join("tsconfig.a.json")) {
+            Err(ConfigError::CircularExtends(cycle)) => assert_eq!(cycle, vec![a.clone(), b, a]),
+            other => panic!("expected a circular extends error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn detect_file_extending_itself() {
+        let path = Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap())
+            .join("test/circular/tsconfig.self.json");
+        let canonical = std::fs::canonicalize(&path).unwrap();
+
+        match parse_file_to_value(&path) {
+            Err(ConfigError::CircularExtends(cycle)) => {
+                assert_eq!(cycle, vec![canonical.clone(), canonical])
+            }
+            other => panic!("expected a circular extends error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn shared_base_is_not_circular() {
+        let path = Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap())
+            .join("test/circular/tsconfig.diamond.json");
+        let config = TsConfig::parse_file(&path).unwrap();
+
+        assert_eq!(config.compiler_options.unwrap().strict, Some(true));
+    }
+
+    #[test]
+    fn ignores_dangling_commas_in_arrays() {
+        let json = r#"{"include": ["a", "b",], "compilerOptions": {"lib": ["dom", "es2015" , ]}}"#;
+        let cfg = TsConfig::parse_str(json).unwrap();
+        assert_eq!(cfg.include, Some(vec!["a".to_string(), "b".to_string()]));
+        assert_eq!(
+            cfg.compiler_options.unwrap().lib,
+            Some(vec![Lib::Dom, Lib::Es2015])
+        );
+
+        let value = parse_to_value(r#"{"files": [["nested",],],}"#).unwrap();
+        assert_eq!(value["files"][0][0], Value::String("nested".to_string()));
+    }
+
+    #[test]
+    fn keeps_commas_inside_strings() {
+        let json = r#"{"include": ["x,]", "y,}", "z\",]"], "exclude": [",",]}"#;
+        let cfg = TsConfig::parse_str(json).unwrap();
+        assert_eq!(
+            cfg.include,
+            Some(vec![
+                "x,]".to_string(),
+                "y,}".to_string(),
+                "z\",]".to_string()
+            ])
+        );
+        assert_eq!(cfg.exclude, Some(vec![",".to_string()]));
+    }
+
+    #[test]
+    fn parse_esnext_lib() {
+        let json = r#"{"compilerOptions":{"lib":["esnext"]}}"#;
+        let config = TsConfig::parse_str(json).unwrap();
+        assert_eq!(
+            config.compiler_options.unwrap().lib,
+            Some(vec![Lib::EsNext])
+        );
+
+        let json = r#"{"compilerOptions":{"lib":["ESNext", "ESNext.Array"]}}"#;
+        let config = TsConfig::parse_str(json).unwrap();
+        assert_eq!(
+            config.compiler_options.unwrap().lib,
+            Some(vec![Lib::EsNext, Lib::EsNextArray])
+        );
+    }
+
+    #[test]
+    fn parse_targets() {
+        let target = |t: &str| {
+            let json = format!(r#"{{"compilerOptions": {{"target": "{}"}}}}"#, t);
+            TsConfig::parse_str(&json)
+                .unwrap()
+                .compiler_options
+                .unwrap()
+                .target
+                .unwrap()
+        };
+
+        assert_eq!(target("es3"), Target::Es3);
+        assert_eq!(target("ES5"), Target::Es5);
+        assert_eq!(target("es6"), Target::Es6);
+        assert_eq!(target("es2015"), Target::Es2015);
+        assert_eq!(target("es7"), Target::Es7);
+        assert_eq!(target("ESNext"), Target::EsNext);
+    }
+
+    #[test]
+    fn parse_es2016_and_es2017_libs() {
+        let json = r#"{"compilerOptions": {"lib": ["es2016.array.include", "es2017.object", "es2017.sharedmemory"]}}"#;
+        let config = TsConfig::parse_str(json).unwrap();
+        let lib = config.compiler_options.unwrap().lib.unwrap();
+        assert_eq!(
+            lib,
+            vec![
+                Lib::Es2016ArrayInclude,
+                Lib::Es2017Object,
+                Lib::Es2017SharedMemory
+            ]
+        );
+        assert_eq!(
+            serde_json::to_value(&lib).unwrap(),
+            serde_json::json!([
+                "es2016.array.include",
+                "es2017.object",
+                "es2017.sharedmemory"
+            ])
+        );
+
+        // TypeScript has no es2015 spelling of these libraries.
+        let json = r#"{"compilerOptions": {"lib": ["es2015.object"]}}"#;
+        let config = TsConfig::parse_str(json).unwrap();
+        assert_eq!(
+            config.compiler_options.unwrap().lib,
+            Some(vec![Lib::Other("es2015.object".to_string())])
+        );
+    }
+
+    #[test]
+    fn parse_es2017_intl_lib() {
+        let json = r#"{"compilerOptions": {"lib": ["es2017.intl"]}}"#;
+        let config = TsConfig::parse_str(json).unwrap();
+        assert_eq!(
+            config.compiler_options.unwrap().lib,
+            Some(vec![Lib::Es2017Intl])
+        );
+    }
+
+    #[test]
+    fn parse_recent_targets() {
+        let json = r#"{"compilerOptions": {"target": "ES2022", "lib": ["esnext.disposable"]}}"#;
+        let compiler_options = TsConfig::parse_str(json).unwrap().compiler_options.unwrap();
+        assert_eq!(compiler_options.target, Some(Target::Es2022));
+        assert_eq!(compiler_options.lib, Some(vec![Lib::EsNextDisposable]));
+
+        for (name, target) in [
+            ("es2021", Target::Es2021),
+            ("es2022", Target::Es2022),
+            ("es2023", Target::Es2023),
+        ] {
+            let json = format!(r#"{{"compilerOptions": {{"target": "{}"}}}}"#, name);
+            let config = TsConfig::parse_str(&json).unwrap();
+            assert_eq!(
+                config.compiler_options.unwrap().target,
+                Some(target.clone())
+            );
+            assert_eq!(
+                serde_json::to_value(target).unwrap(),
+                Value::String(name.to_string())
+            );
+        }
+    }
+
+    #[test]
+    fn parse_node_modules() {
+        for (name, module) in [
+            ("node16", Module::Node16),
+            ("Node18", Module::Node18),
+            ("NodeNext", Module::NodeNext),
+        ] {
+            let json = format!(r#"{{"compilerOptions": {{"module": "{}"}}}}"#, name);
+            let config = TsConfig::parse_str(&json).unwrap();
+            assert_eq!(
+                config.compiler_options.unwrap().module,
+                Some(module.clone())
+            );
+            assert_eq!(
+                serde_json::to_value(module).unwrap(),
+                Value::String(name.to_lowercase())
+            );
+        }
+    }
+
+    #[test]
+    fn parse_module_resolution_modes() {
+        for (name, mode) in [
+            ("node", ModuleResolutionMode::Node),
+            ("classic", ModuleResolutionMode::Classic),
+            ("node10", ModuleResolutionMode::Node10),
+            ("node16", ModuleResolutionMode::Node16),
+            ("nodenext", ModuleResolutionMode::NodeNext),
+            ("bundler", ModuleResolutionMode::Bundler),
+        ] {
+            let json = format!(
+                r#"{{"compilerOptions": {{"moduleResolution": "{}"}}}}"#,
+                name
+            );
+            let config = TsConfig::parse_str(&json).unwrap();
+            assert_eq!(
+                config.compiler_options.unwrap().module_resolution,
+                Some(mode)
+            );
+        }
+    }
+
+    #[test]
+    fn parse_unknown_module_resolution_mode() {
+        let json = r#"{"compilerOptions": {"moduleResolution": "Node12", "strict": true}}"#;
+        let compiler_options = TsConfig::parse_str(json).unwrap().compiler_options.unwrap();
+        assert_eq!(
+            compiler_options.module_resolution,
+            Some(ModuleResolutionMode::Other("Node12".to_string()))
+        );
+        assert_eq!(compiler_options.strict, Some(true));
+
+        let json = r#"{"compilerOptions": {"moduleResolution": "NodeNext"}}"#;
+        let config = TsConfig::parse_str(json).unwrap();
+        assert_eq!(
+            config.compiler_options.unwrap().module_resolution,
+            Some(ModuleResolutionMode::NodeNext)
+        );
+    }
+
+    #[test]
+    fn collects_unknown_compiler_options() {
+        let json = r#"{"compilerOptions": {"noEmit": true, "someNewUnsupportedProperty": {"a": 1}, "strcit": true}}"#;
+        let compiler_options = TsConfig::parse_str(json).unwrap().compiler_options.unwrap();
+
+        assert_eq!(compiler_options.no_emit, Some(true));
+        let unknown = compiler_options.unknown_options();
+        assert_eq!(unknown.len(), 2);
+        assert_eq!(
+            unknown["someNewUnsupportedProperty"],
+            serde_json::json!({"a": 1})
+        );
+        assert_eq!(unknown["strcit"], Value::Bool(true));
+
+        let value = serde_json::to_value(&compiler_options).unwrap();
+        assert_eq!(value["strcit"], Value::Bool(true));
+    }
+
+    #[test]
+    fn syntax_errors_point_into_original_source() {
+        let json = r#"{
+    /* A block comment,
+       spanning lines */
+    "include": ["src",], // trailing comma
+    "compilerOptions": {
+        "strict" true
+    }
+}"#;
+        match TsConfig::parse_str(json) {
+            Err(ConfigError::InvalidJson {
+                line,
+                column,
+                snippet,
+                ..
+            }) => {
+                assert_eq!(line, 6);
+                assert_eq!(column, 18);
+                assert_eq!(snippet, r#"        "strict" true"#);
+            }
+            other => panic!("expected an invalid JSON error, got {:?}", other),
+        }
+
+        match parse_to_value("{\"a\": [1, 2,") {
+            Err(ConfigError::InvalidJson { line, .. }) => assert_eq!(line, 1),
+            other => panic!("expected an invalid JSON error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn type_errors_are_not_syntax_errors() {
+        let json = r#"{"compilerOptions": {"strict": "yes"}}"#;
+        assert!(matches!(
+            TsConfig::parse_str(json),
+            Err(ConfigError::ParseError(_))
+        ));
+    }
+
+    #[test]
+    fn errors_name_the_file_at_fault() {
+        let test_dir = Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap()).join("test");
+
+        match TsConfig::parse_file(&test_dir.join("tsconfig.extends_broken.json")) {
+            Err(ConfigError::InvalidExtends { path, source }) => {
+                assert!(path.ends_with("b/tsconfig.broken.json"));
+                assert!(matches!(*source, ConfigError::InvalidJson { line: 3, .. }));
+            }
+            other => panic!("expected an invalid base file, got {:?}", other),
+        }
+
+        match TsConfig::parse_file(&test_dir.join("b/tsconfig.broken.json")) {
+            Err(ConfigError::FileError { path, source }) => {
+                assert!(path.ends_with("b/tsconfig.broken.json"));
+                assert!(matches!(*source, ConfigError::InvalidJson { line: 3, .. }));
+            }
+            other => panic!("expected a file error, got {:?}", other),
+        }
+
+        match parse_file_to_value(&test_dir.join("a/tsconfig.missing_base.json")) {
+            Err(ConfigError::FileError { path, source }) => {
+                assert!(path.ends_with("a/does_not_exist.json"));
+                assert!(matches!(*source, ConfigError::CouldNotFindFile(_)));
+            }
+            other => panic!("expected a file error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn build_config_from_scratch() {
+        let config = TsConfig::builder()
+            .extends(Extends::Single("./tsconfig.base.json".to_string()))
+            .module(Module::EsNext)
+            .module_resolution(ModuleResolutionMode::Bundler)
+            .jsx(Jsx::ReactJsx)
+            .out_dir("dist")
+            .build();
+
+        let value = serde_json::to_value(&config).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "extends": "./tsconfig.base.json",
+                "compilerOptions": {
+                    "module": "esnext",
+                    "moduleResolution": "bundler",
+                    "jsx": "react-jsx",
+                    "outDir": "dist"
+                }
+            })
+        );
+
+        let parsed = TsConfig::parse_str(&value.to_string()).unwrap();
+        assert_eq!(
+            parsed.compiler_options.unwrap().out_dir,
+            Some("dist".to_string())
+        );
+        assert_eq!(
+            serde_json::to_value(TsConfig::default()).unwrap(),
+            serde_json::json!({})
+        );
+    }
+
+    #[test]
+    fn fill_in_typescript_defaults() {
+        let json = r#"{"compilerOptions": {"target": "es2022", "strict": true, "strictNullChecks": false}}"#;
+        let options = TsConfig::parse_str(json)
+            .unwrap()
+            .compiler_options
+            .unwrap()
+            .with_defaults(Target::Es5);
+
+        assert_eq!(options.target, Some(Target::Es2022));
+        assert_eq!(options.module, Some(Module::Es6));
+        assert_eq!(
+            options.module_resolution,
+            Some(ModuleResolutionMode::Classic)
+        );
+        assert_eq!(options.use_define_for_class_fields, Some(true));
+        assert_eq!(options.no_implicit_any, Some(true));
+        assert_eq!(options.strict_null_checks, Some(false));
+        assert_eq!(options.es_module_interop, Some(false));
+        assert_eq!(options.declaration, Some(false));
+
+        let json = r#"{"compilerOptions": {"module": "nodenext", "composite": true}}"#;
+        let options = TsConfig::parse_str(json)
+            .unwrap()
+            .compiler_options
+            .unwrap()
+            .with_defaults(Target::EsNext);
+
+        assert_eq!(
+            options.module_resolution,
+            Some(ModuleResolutionMode::NodeNext)
+        );
+        assert_eq!(options.es_module_interop, Some(true));
+        assert_eq!(options.allow_synthetic_default_imports, Some(true));
+        assert_eq!(options.declaration, Some(true));
+        assert_eq!(options.incremental, Some(true));
+        assert_eq!(options.strict, Some(false));
+    }
+
+    #[test]
+    fn parse_watch_options() {
+        let json = r#"{
+            "watchOptions": {
+                "watchFile": "useFsEventsOnParentDirectory",
+                "watchDirectory": "fixedChunkSizePolling",
+                "fallbackPolling": "dynamicPriority",
+                "synchronousWatchDirectory": true,
+                "excludeDirectories": ["**/node_modules", "_build"]
+            }
+        }"#;
+        let watch_options = TsConfig::parse_str(json).unwrap().watch_options.unwrap();
+
+        assert_eq!(
+            watch_options.watch_file,
+            Some(WatchFile::UseFsEventsOnParentDirectory)
+        );
+        assert_eq!(
+            watch_options.watch_directory,
+            Some(WatchDirectory::FixedChunkSizePolling)
+        );
+        assert_eq!(
+            watch_options.fallback_polling,
+            Some(FallbackPolling::DynamicPriority)
+        );
+        assert_eq!(watch_options.synchronous_watch_directory, Some(true));
+        assert_eq!(
+            watch_options.exclude_directories,
+            Some(vec!["**/node_modules".to_string(), "_build".to_string()])
+        );
+
+        let value = serde_json::to_value(&watch_options).unwrap();
+        assert_eq!(value["fallbackPolling"], "dynamicPriority");
+    }
+
+    #[test]
+    fn resolve_path_mappings() {
+        let json = r#"{
+            "compilerOptions": {
+                "paths": {
+                    "*": ["./types/*"],
+                    "@app/*": ["./src/app/*"],
+                    "@app/shared/*": ["./src/shared/*", "./vendor/shared/*"],
+                    "config": ["./config/index.ts"],
+                    "@bad/*/*": ["./never/*"]
+                }
+            }
+        }"#;
+        let options = TsConfig::parse_str(json).unwrap().compiler_options.unwrap();
+
+        assert_eq!(
+            options.resolve_path_mapping("@app/shared/button"),
+            Some(vec![
+                "./src/shared/button".to_string(),
+                "./vendor/shared/button".to_string()
+            ])
+        );
+        assert_eq!(
+            options.resolve_path_mapping("@app/main"),
+            Some(vec!["./src/app/main".to_string()])
+        );
+        assert_eq!(
+            options.resolve_path_mapping("config"),
+            Some(vec!["./config/index.ts".to_string()])
+        );
+        assert_eq!(
+            options.resolve_path_mapping("react"),
+            Some(vec!["./types/react".to_string()])
+        );
+        assert_eq!(
+            CompilerOptions::default().resolve_path_mapping("react"),
+            None
+        );
+    }
+
+    #[test]
+    fn list_included_files() {
+        let project_dir =
+            Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap()).join("test/project");
+        let relative = |files: Vec<PathBuf>| -> Vec<String> {
+            files
+                .iter()
+                .map(|f| {
+                    f.strip_prefix(&project_dir)
+                        .unwrap()
+                        .to_string_lossy()
+                        .replace('\\', "/")
+                })
+                .collect()
+        };
+
+        let config = TsConfig::parse_file(&project_dir.join("tsconfig.json")).unwrap();
+        assert_eq!(
+            relative(config.included_files(&project_dir).unwrap()),
+            vec![
+                "scripts/build.ts",
+                "src/components/App.tsx",
+                "src/globals.d.ts",
+                "src/index.ts"
+            ]
+        );
+
+        let json = r#"{
+            "compilerOptions": {"allowJs": true},
+            "files": ["./scripts/build.ts"],
+            "include": ["src/**/*", "../project/scripts/*.ts"],
+            "exclude": ["**/*.d.ts"]
+        }"#;
+        let config = TsConfig::parse_str(json).unwrap();
+        assert_eq!(
+            relative(config.included_files(&project_dir).unwrap()),
+            vec![
+                "scripts/build.ts",
+                "src/components/App.tsx",
+                "src/index.ts",
+                "src/legacy.js"
+            ]
+        );
+
+        let json = r#"{
+            "files": ["src/index.ts"],
+            "include": ["src"],
+            "exclude": ["src/*.ts"]
+        }"#;
+        let config = TsConfig::parse_str(json).unwrap();
+        assert_eq!(
+            relative(config.included_files(&project_dir).unwrap()),
+            vec!["src/index.ts", "src/components/App.tsx"]
+        );
+
+        let json = r#"{"files": ["src/index.ts"]}"#;
+        let config = TsConfig::parse_str(json).unwrap();
+        assert_eq!(
+            relative(config.included_files(&project_dir).unwrap()),
+            vec!["src/index.ts"]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn list_included_files_through_symlink_loop() {
+        // test/symlinks/src/loop links back to test/symlinks.
+        let project_dir =
+            Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap()).join("test/symlinks");
+        assert!(project_dir.join("src/loop").is_dir());
+
+        let files = TsConfig::default().included_files(&project_dir).unwrap();
+        assert_eq!(files, vec![project_dir.join("src/a.ts")]);
+
+        let config = TsConfig::parse_str(r#"{"include": ["src/**/*"]}"#).unwrap();
+        assert_eq!(
+            config.included_files(&project_dir).unwrap(),
+            vec![project_dir.join("src/a.ts")]
+        );
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn parse_file_async_follows_extends() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let test_dir = Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap()).join("test");
+
+        let config = runtime
+            .block_on(TsConfig::parse_file_async(
+                &test_dir.join("a/tsconfig.packages.json"),
+            ))
+            .unwrap();
+        let sync_config = TsConfig::parse_file(&test_dir.join("a/tsconfig.packages.json")).unwrap();
+        assert_eq!(
+            serde_json::to_value(config).unwrap(),
+            serde_json::to_value(sync_config).unwrap()
+        );
+
+        let error = runtime
+            .block_on(parse_file_to_value_async(
+                &test_dir.join("circular/tsconfig.a.json"),
+            ))
+            .unwrap_err();
+        assert!(matches!(error, ConfigError::CircularExtends(_)));
+    }
+
+    #[test]
+    fn record_where_options_came_from() {
+        let test_dir = Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap()).join("test");
+        let path = test_dir.join("tsconfig.extends_multiple.json");
+        let (config, provenance) = TsConfig::parse_file_with_provenance(&path).unwrap();
+
+        let options = config.compiler_options.unwrap();
+        assert_eq!(options.strict, Some(true));
+        assert!(provenance["strict"].ends_with("b/tsconfig.strict.json"));
+        assert!(provenance["jsx"].ends_with("b/tsconfig.strict.json"));
+        assert_eq!(provenance["declaration"], path);
+        assert!(provenance["useDefineForClassFields"].ends_with("b/tsconfig.base.json"));
+        assert_eq!(provenance.get("allowJs"), None);
+    }
+
+    #[test]
+    fn derived_config_overrides_its_base() {
+        let path = Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap())
+            .join("test/override/tsconfig.json");
+        let config = TsConfig::parse_file(&path).unwrap();
+        let options = config.compiler_options.unwrap();
+
+        assert_eq!(options.strict, Some(false));
+        assert_eq!(options.target, Some(Target::Es5));
+
+        let paths = options.paths.unwrap();
+        assert_eq!(paths.len(), 1);
+        assert_eq!(paths["@app/*"], vec!["src/*".to_string()]);
+
+        assert!(config.references.is_none());
+    }
+
+    #[test]
+    fn arrays_are_replaced_not_merged() {
+        let path = Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap())
+            .join("test/override/tsconfig.json");
+        let config = TsConfig::parse_file(&path).unwrap();
+
+        assert_eq!(config.include, Some(vec!["src".to_string()]));
+        assert_eq!(config.exclude, Some(vec!["**/*.test.ts".to_string()]));
+
+        let mut child = serde_json::json!({"compilerOptions": {"lib": ["dom"]}});
+        merge(
+            &mut child,
+            serde_json::json!({"compilerOptions": {"lib": ["es2015", "es2016"], "types": ["node"]}}),
+        );
+        assert_eq!(
+            child,
+            serde_json::json!({"compilerOptions": {"lib": ["dom"], "types": ["node"]}})
+        );
+    }
+
+    #[test]
+    fn parse_jsconfig_with_javascript_defaults() {
+        let path =
+            Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap()).join("test/jsconfig.json");
+        let config = TsConfig::parse_jsconfig_file(&path).unwrap();
+        assert!(config.is_jsconfig);
+
+        let options = config.compiler_options.unwrap();
+        assert_eq!(options.allow_js, Some(true));
+        assert_eq!(options.max_node_module_js_depth, Some(2));
+        assert_eq!(options.skip_lib_check, Some(true));
+        assert_eq!(options.no_emit, Some(false));
+        assert_eq!(options.check_js, Some(true));
+
+        assert!(!TsConfig::parse_file(&path).unwrap().is_jsconfig);
+    }
+
+    #[test]
+    fn parse_from_bytes_and_readers() {
+        let json = br#"{"include": ["src"], "compilerOptions": {"jsx": "preserve",},}"#;
+
+        let config = TsConfig::parse_bytes(json).unwrap();
+        assert_eq!(config.include, Some(vec!["src".to_string()]));
+
+        let config = TsConfig::parse_reader(std::io::Cursor::new(json.to_vec())).unwrap();
+        assert_eq!(config.compiler_options.unwrap().jsx, Some(Jsx::Preserve));
+
+        let invalid: &[u8] = b"{\"files\": [\"caf\xe9.ts\"]}";
+        assert!(matches!(
+            TsConfig::parse_bytes(invalid),
+            Err(ConfigError::InvalidUtf8(_))
+        ));
+        assert!(matches!(
+            TsConfig::parse_reader(invalid),
+            Err(ConfigError::InvalidUtf8(_))
+        ));
+
+        // Only a failure to read is reported as an I/O error.
+        struct FailingReader;
+        impl Read for FailingReader {
+            fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+                Err(std::io::Error::new(
+                    std::io::ErrorKind::BrokenPipe,
+                    "closed",
+                ))
+            }
+        }
+        assert!(matches!(
+            TsConfig::parse_reader(FailingReader),
+            Err(ConfigError::CouldNotFindFile(_))
+        ));
+    }
+
+    #[test]
+    fn keeps_comment_like_sequences_inside_strings() {
+        let json = r#"{
+            // A real comment, with a trailing comma after it: ,}
+            "include": ["src/**/*", "types/*.d.ts", /* another */ "a//b",],
+            "compilerOptions": {
+                "baseUrl": "https://cdn.example.com/*,}",
+                "jsxFactory": "h /* not a comment */",
+            },
+        }"#;
+
+        let config = TsConfig::parse_str(json).unwrap();
+        assert_eq!(
+            config.include,
+            Some(vec![
+                "src/**/*".to_string(),
+                "types/*.d.ts".to_string(),
+                "a//b".to_string()
+            ])
+        );
+        let options = config.compiler_options.unwrap();
+        assert_eq!(
+            options.base_url,
+            Some("https://cdn.example.com/*,}".to_string())
+        );
+        assert_eq!(
+            options.jsx_factory,
+            Some("h /* not a comment */".to_string())
+        );
+
+        let value = parse_to_value(json).unwrap();
+        assert_eq!(value["include"][0], "src/**/*");
+        assert_eq!(
+            value["compilerOptions"]["baseUrl"],
+            "https://cdn.example.com/*,}"
+        );
+    }
+
+    #[test]
+    fn parse_module_detection() {
+        for (json, expected) in [
+            ("auto", ModuleDetection::Auto),
+            ("legacy", ModuleDetection::Legacy),
+            ("force", ModuleDetection::Force),
+        ]
+        .iter()
+        {
+            let json = format!(
+                r#"{{"compilerOptions": {{"moduleDetection": "{}", "verbatimModuleSyntax": true}}}}"#,
+                json
+            );
+            let options = TsConfig::parse_str(&json)
+                .unwrap()
+                .compiler_options
+                .unwrap();
+            assert_eq!(options.module_detection, Some(*expected));
+            assert_eq!(options.verbatim_module_syntax, Some(true));
+        }
+    }
+
+    #[test]
+    fn parse_imports_not_used_as_values() {
+        let json = r#"{"compilerOptions": {"importsNotUsedAsValues": "Preserve"}}"#;
+        let options = TsConfig::parse_str(json).unwrap().compiler_options.unwrap();
+        assert_eq!(
+            options.imports_not_used_as_values,
+            Some(ImportsNotUsedAsValues::Preserve)
+        );
+        assert_eq!(
+            serde_json::to_value(&options).unwrap()["importsNotUsedAsValues"],
+            "preserve"
+        );
+
+        let json = r#"{"compilerOptions": {"importsNotUsedAsValues": "elide"}}"#;
+        let options = TsConfig::parse_str(json).unwrap().compiler_options.unwrap();
+        assert_eq!(
+            options.imports_not_used_as_values,
+            Some(ImportsNotUsedAsValues::Other("elide".to_string()))
+        );
+    }
+
+    #[test]
+    fn parse_package_json_resolution_options() {
+        let json = r#"{
+            "compilerOptions": {
+                "moduleResolution": "bundler",
+                "customConditions": ["development", "browser"],
+                "resolvePackageJsonExports": true,
+                "resolvePackageJsonImports": false
+            }
+        }"#;
+        let options = TsConfig::parse_str(json).unwrap().compiler_options.unwrap();
+
+        assert_eq!(
+            options.custom_conditions,
+            Some(vec!["development".to_string(), "browser".to_string()])
+        );
+        assert_eq!(options.resolve_package_json_exports, Some(true));
+        assert_eq!(options.resolve_package_json_imports, Some(false));
+        assert!(options.unknown_options().is_empty());
+    }
+
+    #[test]
+    fn display_canonical_names() {
+        assert_eq!(Target::Es2015.to_string(), "es2015");
+        assert_eq!(Target::EsNext.as_str(), "esnext");
+        assert_eq!(Lib::DomIterable.to_string(), "dom.iterable");
+        assert_eq!(Module::CommonJs.to_string(), "commonjs");
+        assert_eq!(ModuleResolutionMode::NodeNext.to_string(), "nodenext");
+        assert_eq!(ImportsNotUsedAsValues::Error.to_string(), "error");
+
+        let args: Vec<String> = [Lib::Es2017, Lib::Dom]
+            .iter()
+            .map(|lib| lib.to_string())
+            .collect();
+        assert_eq!(args.join(","), "es2017,dom");
+    }
+
+    #[test]
+    fn expand_config_dir_relative_to_the_extending_file() {
+        let app_dir =
+            Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap()).join("test/config_dir/app");
+        let config = TsConfig::parse_file(&app_dir.join("tsconfig.json")).unwrap();
+        let dir = app_dir.to_string_lossy();
+
+        assert_eq!(config.include, Some(vec![format!("{}/src", dir)]));
+        let options = config.compiler_options.unwrap();
+        assert_eq!(options.out_dir, Some(format!("{}/dist", dir)));
+        assert_eq!(
+            options.paths.unwrap()["@/*"],
+            vec![format!("{}/src/*", dir)]
+        );
+        assert_eq!(options.jsx_factory, Some("h".to_string()));
+
+        let config =
+            TsConfig::parse_str(r#"{"compilerOptions": {"outDir": "${configDir}/dist"}}"#).unwrap();
+        assert_eq!(
+            config.compiler_options.unwrap().out_dir,
+            Some("${configDir}/dist".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_plugins() {
+        let json = r#"{
+            "compilerOptions": {
+                "plugins": [
+                    {"name": "typescript-plugin-css-modules"},
+                    {"name": "@styled/typescript-styled-plugin", "lint": {"validProperties": ["label"]}}
+                ]
+            }
+        }"#;
+        let options = TsConfig::parse_str(json).unwrap().compiler_options.unwrap();
+        let plugins = options.plugins.as_ref().unwrap();
+
+        assert_eq!(plugins.len(), 2);
+        assert_eq!(plugins[0].name, "typescript-plugin-css-modules");
+        assert!(plugins[0].options.is_empty());
+        assert_eq!(
+            plugins[1].options["lint"],
+            serde_json::json!({"validProperties": ["label"]})
+        );
+
+        let value = serde_json::to_value(&options).unwrap();
+        assert_eq!(
+            value["plugins"][1],
+            serde_json::json!({"name": "@styled/typescript-styled-plugin", "lint": {"validProperties": ["label"]}})
+        );
+    }
+
+    #[test]
+    fn validate_option_constraints() {
+        let validate = |json: &str| TsConfig::parse_str(json).unwrap().validate();
+
+        assert!(validate(r#"{"compilerOptions": {"composite": true}}"#).is_empty());
+        assert!(
+            validate(r#"{"compilerOptions": {"outFile": "out.js", "module": "amd"}}"#).is_empty()
+        );
+        assert!(validate("{}").is_empty());
+
+        assert_eq!(
+            validate(r#"{"compilerOptions": {"composite": true, "incremental": false}}"#),
+            vec![ValidationError::CompositeWithoutIncremental]
+        );
+        assert_eq!(
+            validate(
+                r#"{"compilerOptions": {"emitDeclarationOnly": true, "declarationMap": true, "noEmit": true}}"#
+            ),
+            vec![
+                ValidationError::RequiresDeclaration("emitDeclarationOnly"),
+                ValidationError::RequiresDeclaration("declarationMap"),
+                ValidationError::ConflictingOptions("noEmit", "emitDeclarationOnly"),
+            ]
+        );
+
+        let errors =
+            validate(r#"{"compilerOptions": {"outFile": "out.js", "module": "commonjs"}}"#);
+        assert_eq!(
+            errors,
+            vec![ValidationError::OutFileWithModule(Module::CommonJs)]
+        );
+        assert_eq!(
+            errors[0].to_string(),
+            "Only 'amd' and 'system' modules are supported alongside --outFile, not 'commonjs'"
+        );
+
+        let errors = validate(
+            r#"{"compilerOptions": {"outFile": "out.js", "outDir": "dist", "declarationDir": "types", "module": "es2015"}}"#,
+        );
+        assert_eq!(
+            errors,
+            vec![
+                ValidationError::ConflictingOptions("declarationDir", "outFile"),
+                ValidationError::OutFileWithModule(Module::Es2015),
+            ]
+        );
+        assert_eq!(
+            errors[0].to_string(),
+            "Option 'declarationDir' cannot be specified with option 'outFile'"
+        );
+    }
+
+    #[test]
+    fn parse_reference_forms() {
+        let references = |json: &str| TsConfig::parse_str(json).unwrap().references;
+
+        assert_eq!(
+            references(r#"{"references": true}"#),
+            Some(References::Bool(true))
+        );
+        assert_eq!(
+            references(r#"{"references": []}"#),
+            Some(References::References(vec![]))
+        );
+
+        let parsed = references(
+            r#"{"references": [{"path": "../core", "prepend": true}, {"path": "../utils", "circular": true}]}"#,
+        );
+        let list = match parsed {
+            Some(References::References(list)) => list,
+            other => panic!("expected a list of references, got {:?}", other),
+        };
+        assert_eq!(list[0].path, "../core");
+        assert_eq!(list[0].prepend, Some(true));
+        assert!(list[0].extra.is_empty());
+        assert_eq!(list[1].prepend, None);
+        assert_eq!(list[1].extra["circular"], Value::Bool(true));
+        assert_eq!(
+            serde_json::to_value(&list[1]).unwrap(),
+            serde_json::json!({"path": "../utils", "circular": true})
+        );
+    }
+
+    #[test]
+    fn reject_malformed_references() {
+        let error = |json: &str| match TsConfig::parse_str(json) {
+            Err(ConfigError::ParseError(e)) => e.to_string(),
+            other => panic!("expected a parse error, got {:?}", other),
+        };
+
+        assert!(error(r#"{"references": 0}"#)
+            .starts_with("expected a boolean or a list of project references, found 0"));
+        assert!(
+            error(r#"{"references": [{"path": "../a"}, {"prepend": true}]}"#)
+                .starts_with("invalid project reference at index 1: missing field `path`")
+        );
+    }
+
+    #[test]
+    fn resolve_option_paths_against_defining_file() {
+        let test_dir = Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap()).join("test");
+        let path = test_dir.join("tsconfig.extends_paths.json");
+        let (config, provenance) = TsConfig::parse_file_with_provenance(&path).unwrap();
+        let options = config.compiler_options.unwrap();
+
+        let base_url_dir = provenance["baseUrl"].parent().unwrap();
+        assert_eq!(
+            options.resolved_base_url(base_url_dir),
+            Some(test_dir.join("b/src"))
+        );
+        let out_dir_dir = provenance["outDir"].parent().unwrap();
+        assert_eq!(
+            options.resolved_out_dir(out_dir_dir),
+            Some(test_dir.join("dist"))
+        );
+        assert_eq!(
+            options.resolved_declaration_dir(out_dir_dir),
+            Some(PathBuf::from("/tmp/types"))
+        );
+    }
+
+    #[test]
+    fn expand_strict_flags() {
+        let flags = |json: &str| {
+            TsConfig::parse_str(json)
+                .unwrap()
+                .compiler_options
+                .unwrap_or_default()
+                .effective_strict_flags()
+        };
+
+        assert_eq!(flags("{}"), StrictFlags::default());
+
+        let strict = flags(r#"{"compilerOptions": {"strict": true, "noImplicitAny": false}}"#);
+        assert!(!strict.no_implicit_any);
+        assert!(strict.strict_null_checks);
+        assert!(strict.strict_function_types);
+        assert!(strict.strict_bind_call_apply);
+        assert!(strict.strict_property_initialization);
+        assert!(strict.no_implicit_this);
+        assert!(strict.use_unknown_in_catch_variables);
+        assert!(strict.strict_builtin_iterator_return);
+        assert!(strict.always_strict);
+
+        let loose = flags(r#"{"compilerOptions": {"strictNullChecks": true}}"#);
+        assert_eq!(
+            loose,
+            StrictFlags {
+                strict_null_checks: true,
+                ..StrictFlags::default()
+            }
+        );
+    }
+
+    #[test]
+    fn override_catch_variable_strictness() {
+        let json = r#"{"compilerOptions": {"strict": true, "useUnknownInCatchVariables": false, "strictBuiltinIteratorReturn": false}}"#;
+        let options = TsConfig::parse_str(json).unwrap().compiler_options.unwrap();
+        assert_eq!(options.use_unknown_in_catch_variables, Some(false));
+
+        let flags = options.effective_strict_flags();
+        assert!(!flags.use_unknown_in_catch_variables);
+        assert!(!flags.strict_builtin_iterator_return);
+        assert!(flags.strict_null_checks);
+
+        let defaults = options.with_defaults(Target::Es2020);
+        assert_eq!(defaults.use_unknown_in_catch_variables, Some(false));
+        assert_eq!(defaults.no_implicit_this, Some(true));
+    }
+
+    #[test]
+    fn parse_numeric_target_and_module() {
+        let options = |json: &str| TsConfig::parse_str(json).unwrap().compiler_options.unwrap();
+
+        let parsed = options(r#"{"compilerOptions": {"target": 2015, "module": 2020}}"#);
+        assert_eq!(parsed.target, Some(Target::Es2015));
+        assert_eq!(parsed.module, Some(Module::Es2020));
+
+        let parsed = options(r#"{"compilerOptions": {"target": 5, "module": 6}}"#);
+        assert_eq!(parsed.target, Some(Target::Es5));
+        assert_eq!(parsed.module, Some(Module::Es6));
+
+        assert!(TsConfig::parse_str(r#"{"compilerOptions": {"target": true}}"#).is_err());
+    }
+
+    #[test]
+    fn parse_names_from_strings() {
+        assert_eq!("ES2020".parse(), Ok(Target::Es2020));
+        assert_eq!("esnext".parse(), Ok(Module::EsNext));
+        assert_eq!("DOM.Iterable".parse(), Ok(Lib::DomIterable));
+        assert_eq!("Bundler".parse(), Ok(ModuleResolutionMode::Bundler));
+        assert_eq!("remove".parse(), Ok(ImportsNotUsedAsValues::Remove));
+
+        let target: Target = "es2099".parse().unwrap();
+        assert!(matches!(target, Target::Other(_)));
+    }
+
+    #[test]
+    fn compute_effective_libs() {
+        let libs = |json: &str| {
+            TsConfig::parse_str(json)
+                .unwrap()
+                .compiler_options
+                .unwrap_or_default()
+                .effective_libs()
+        };
+
+        assert_eq!(
+            libs("{}"),
+            vec![
+                Lib::Es5,
+                Lib::Dom,
+                Lib::WebWorkerImportScripts,
+                Lib::ScriptHost
+            ]
+        );
+        assert_eq!(
+            libs(r#"{"compilerOptions": {"target": "es2020"}}"#),
+            vec![
+                Lib::Es2020,
+                Lib::Dom,
+                Lib::WebWorkerImportScripts,
+                Lib::ScriptHost,
+                Lib::DomIterable,
+                Lib::DomAsyncIterable
+            ]
+        );
+        assert_eq!(
+            libs(r#"{"compilerOptions": {"target": "esnext"}}"#)[0],
+            Lib::EsNext
+        );
+        assert_eq!(
+            libs(r#"{"compilerOptions": {"target": "es2020", "lib": ["es2019", "webworker"]}}"#),
+            vec![Lib::Es2019, Lib::WebWorker]
+        );
+        assert!(libs(r#"{"compilerOptions": {"noLib": true, "lib": ["dom"]}}"#).is_empty());
+        assert_eq!(Lib::DomAsyncIterable.to_string(), "dom.asynciterable");
+    }
+
+    #[test]
+    fn report_deprecated_options() {
+        let json = r#"{"compilerOptions": {"strict": true}}"#;
+        assert!(TsConfig::parse_str(json)
+            .unwrap()
+            .deprecation_warnings()
+            .is_empty());
+
+        let json = r#"{"compilerOptions": {"diagnostics": false, "preserveValueImports": true, "suppressImplicitAnyIndexErrors": true}}"#;
+        assert_eq!(
+            TsConfig::parse_str(json).unwrap().deprecation_warnings(),
+            vec![
+                "'diagnostics' is deprecated, use 'extendedDiagnostics' instead",
+                "'preserveValueImports' is deprecated, use 'verbatimModuleSyntax' instead",
+                "'suppressImplicitAnyIndexErrors' is deprecated and has no effect",
+            ]
+        );
+    }
+
+    #[test]
+    fn load_project_references() {
+        let refs_dir = Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap()).join("test/refs");
+        let config = TsConfig::parse_file(&refs_dir.join("app/tsconfig.json")).unwrap();
+
+        let references = config.load_references(&refs_dir.join("app")).unwrap();
+        let paths: Vec<&PathBuf> = references.iter().map(|(path, _)| path).collect();
+        assert_eq!(
+            paths,
+            vec![
+                &refs_dir.join("core/tsconfig.json"),
+                &refs_dir.join("utils/tsconfig.build.json")
+            ]
+        );
+
+        let core = references[0].1.compiler_options.as_ref().unwrap();
+        assert_eq!(
+            core.out_dir,
+            Some(format!("{}/dist", refs_dir.join("core").display()))
+        );
+        let utils = references[1].1.compiler_options.as_ref().unwrap();
+        assert_eq!(
+            utils.resolved_out_dir(&refs_dir.join("utils")),
+            Some(refs_dir.join("utils/lib"))
+        );
+
+        assert!(TsConfig::default()
+            .load_references(&refs_dir)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn detect_circular_references() {
+        let cycle_dir =
+            Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap()).join("test/refs/cycle");
+        let config = TsConfig::parse_file(&cycle_dir.join("a/tsconfig.json")).unwrap();
+
+        match config.build_order(&cycle_dir.join("a")) {
+            Err(ConfigError::CircularReferences(cycle)) => assert_eq!(
+                cycle,
+                vec![
+                    cycle_dir.join("b/tsconfig.json"),
+                    cycle_dir.join("a/tsconfig.json"),
+                    cycle_dir.join("b/tsconfig.json")
+                ]
+            ),
+            other => panic!("expected a reference cycle, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn round_trip_schema() {
+        let json = r#"{"$schema": "https://json.schemastore.org/tsconfig", "files": ["index.ts"]}"#;
+        let config = TsConfig::parse_str(json).unwrap();
+        assert_eq!(
+            config.schema.as_deref(),
+            Some("https://json.schemastore.org/tsconfig")
+        );
+
+        let value = serde_json::to_value(&config).unwrap();
+        assert_eq!(value["$schema"], "https://json.schemastore.org/tsconfig");
+    }
+
+    #[test]
+    fn reject_unknown_top_level_keys_in_strict_mode() {
+        let json = r#"{"$schema": "https://json.schemastore.org/tsconfig", "compileOnSave": true, "fils": []}"#;
+        match TsConfig::parse_str_strict(json) {
+            Err(ConfigError::UnknownOption(option)) => assert_eq!(option, "fils"),
+            other => panic!("expected an unknown option, got {:?}", other),
+        }
+
+        let json = r#"{"compileOnSave": true, "files": [], "compilerOptions": {"strict": true}}"#;
+        let config = TsConfig::parse_str_strict(json).unwrap();
+        assert_eq!(config.compile_on_save, Some(true));
+        assert_eq!(
+            serde_json::to_value(&config).unwrap()["compileOnSave"],
+            true
+        );
+    }
+
+    #[test]
+    fn round_trip_preserves_equality() {
+        let path = Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap())
+            .join("test/tsconfig.default.json");
+        let config = TsConfig::parse_file(&path).unwrap();
+
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(TsConfig::parse_str(&json).unwrap(), config);
+    }
+
+    #[test]
+    fn parse_type_acquisition() {
+        let config = TsConfig::parse_str(r#"{"typeAcquisition": true}"#).unwrap();
+        assert_eq!(config.type_acquisition, Some(TypeAcquisition::Bool(true)));
+
+        let json = r#"{
+            "typeAcquisition": {
+                "enable": true,
+                "include": ["jest"],
+                "exclude": ["jquery"],
+                "disableFilenameBasedTypeAcquisition": true
+            }
+        }"#;
+        let config = TsConfig::parse_str(json).unwrap();
+        assert_eq!(
+            config.type_acquisition,
+            Some(TypeAcquisition::Object {
+                enable: true,
+                include: Some(vec!["jest".to_string()]),
+                exclude: Some(vec!["jquery".to_string()]),
+                disable_filename_based_type_acquisition: Some(true),
+            })
+        );
+
+        let value = serde_json::to_value(&config).unwrap();
+        assert_eq!(
+            value["typeAcquisition"]["disableFilenameBasedTypeAcquisition"],
+            true
+        );
+    }
+
+    #[test]
+    fn merge_configurations_in_memory() {
+        let base = TsConfig::builder()
+            .extends(Extends::Single("./tsconfig.root.json".to_string()))
+            .include(vec!["src".to_string()])
+            .references(References::Bool(true))
+            .jsx(Jsx::React)
+            .out_dir("dist")
+            .build();
+        let config = TsConfig::builder()
+            .include(vec!["lib".to_string()])
+            .out_dir("build")
+            .build();
+
+        let merged = config.merged_with(base);
+        assert_eq!(merged.include, Some(vec!["lib".to_string()]));
+        assert_eq!(merged.extends, None);
+        assert_eq!(merged.references, None);
+        let options = merged.compiler_options.unwrap();
+        assert_eq!(options.jsx, Some(Jsx::React));
+        assert_eq!(options.out_dir.as_deref(), Some("build"));
+    }
+
+    #[test]
+    fn merge_configurations_field_by_field() {
+        let mut base = TsConfig::parse_str(
+            r#"{
+                "compilerOptions": {"target": "es2020", "futureFlag": 1, "otherFlag": "a"},
+                "watchOptions": {"watchFile": "useFsEvents", "excludeFiles": ["a.ts"]},
+                "typeAcquisition": {"enable": true, "include": ["jquery"], "exclude": ["lodash"]}
+            }"#,
+        )
+        .unwrap();
+        base.is_jsconfig = true;
+        let mut config = TsConfig::parse_str(
+            r#"{
+                "compilerOptions": {"otherFlag": "b"},
+                "watchOptions": {"excludeFiles": ["b.ts"]},
+                "typeAcquisition": {"exclude": ["react"]}
+            }"#,
+        )
+        .unwrap();
+        // Values that would not survive a trip through JSON are carried over as they are.
+        let options = config.compiler_options.as_mut().unwrap();
+        options
+            .extra
+            .insert("target".to_string(), serde_json::json!(5.5));
+
+        let merged = config.merged_with(base);
+        assert!(!merged.is_jsconfig);
+
+        let options = merged.compiler_options.unwrap();
+        assert_eq!(options.target, Some(Target::Es2020));
+        assert_eq!(options.extra["futureFlag"], Value::from(1));
+        assert_eq!(options.extra["otherFlag"], Value::from("b"));
+        assert_eq!(options.extra["target"], serde_json::json!(5.5));
 
-        let r = match s.as_str() {
-            "COMMONJS" => Module::CommonJs,
-            "ESNEXT" => Module::EsNext,
-            "ES6" => Module::Es6,
-            "ES2015" => Module::Es2015,
-            "ES2020" => Module::Es2020,
-            "NONE" => Module::None,
-            "UMD" => Module::Umd,
-            "AMD" => Module::Amd,
-            "SYSTEM" => Module::System,
-            other => Module::Other(other.to_string()),
-        };
+        let watch_options = merged.watch_options.unwrap();
+        assert_eq!(watch_options.watch_file, Some(WatchFile::UseFsEvents));
+        assert_eq!(watch_options.exclude_files, Some(vec!["b.ts".to_string()]));
 
-        Ok(r)
+        assert_eq!(
+            merged.type_acquisition,
+            Some(TypeAcquisition::Object {
+                enable: false,
+                include: Some(vec!["jquery".to_string()]),
+                exclude: Some(vec!["react".to_string()]),
+                disable_filename_based_type_acquisition: None,
+            })
+        );
+
+        let config = TsConfig {
+            type_acquisition: Some(TypeAcquisition::Bool(true)),
+            ..TsConfig::default()
+        };
+        let base = TsConfig {
+            type_acquisition: Some(TypeAcquisition::Object {
+                enable: false,
+                include: Some(vec!["jquery".to_string()]),
+                exclude: None,
+                disable_filename_based_type_acquisition: None,
+            }),
+            ..TsConfig::default()
+        };
+        assert_eq!(
+            config.merged_with(base).type_acquisition,
+            Some(TypeAcquisition::Bool(true))
+        );
     }
-}
 
-#[cfg(test)]
-mod test {
-    use super::*;
     #[test]
-    fn parse_jsx() {
-        let json = r#"{"compilerOptions": {"jsx": "react-jsx"}}"#;
+    fn set_options_in_source() {
+        let source = "{\n    \"files\": [\"index.ts\"] /* entry */\n}\n";
 
-        let config = TsConfig::parse_str(json).unwrap();
-        assert_eq!(config.compiler_options.unwrap().jsx, Some(Jsx::ReactJsx));
+        let updated =
+            set_option_in_source(source, &["compilerOptions", "strict"], &Value::Bool(true))
+                .unwrap();
+        assert_eq!(
+            updated,
+            "{\n    \"files\": [\"index.ts\"], /* entry */\n    \"compilerOptions\": {\"strict\":true}\n}\n"
+        );
+
+        let updated = set_option_in_source(
+            r#"{"compilerOptions": {}}"#,
+            &["compilerOptions", "jsx"],
+            &Value::from("react"),
+        )
+        .unwrap();
+        assert_eq!(updated, r#"{"compilerOptions": {"jsx": "react"}}"#);
+
+        let updated = set_option_in_source(
+            r#"{"files": ["a.ts"]}"#,
+            &["files"],
+            &Value::from(vec!["b.ts"]),
+        )
+        .unwrap();
+        assert_eq!(updated, r#"{"files": ["b.ts"]}"#);
+
+        match set_option_in_source(r#"{"files": []}"#, &["files", "a"], &Value::Null) {
+            Err(ConfigError::NotAnObject(path)) => assert_eq!(path, "files"),
+            other => panic!("expected a non-object error, got {:?}", other),
+        }
     }
 
     #[test]
-    fn parse_paths() {
-        let json = r#"{
-        "compilerOptions": {
-            "baseUrl": "src",
-            "paths": {
-                "tests/*": ["tests/*"],
-                "blah": ["bloop"]
+    fn parse_file_with_byte_order_mark() {
+        let path =
+            Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap()).join("test/tsconfig.bom.json");
+        let config = TsConfig::parse_file(&path).unwrap();
+        assert_eq!(config.compiler_options.unwrap().strict, Some(true));
+
+        match TsConfig::parse_str("\u{feff}{\"files\" []}") {
+            Err(ConfigError::InvalidJson { line, column, .. }) => {
+                assert_eq!((line, column), (1, 13));
             }
+            other => panic!("expected invalid JSON, got {:?}", other),
         }
     }
-        
-        "#;
 
+    #[test]
+    fn collect_libs_into_a_set() {
+        let json =
+            r#"{"compilerOptions": {"lib": ["dom", "es2017", "DOM", "esnext.asynciterable"]}}"#;
+        let options = TsConfig::parse_str(json).unwrap().compiler_options.unwrap();
+
+        let libs: HashSet<Lib> = options.lib.unwrap().into_iter().collect();
+        assert_eq!(libs.len(), 3);
+        assert!(libs.contains(&Lib::Dom));
+        assert!(libs.contains(&Lib::EsNextAsyncIterable));
+    }
+
+    #[test]
+    fn parse_file_extending_a_directory() {
+        let path = Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap())
+            .join("test/tsconfig.extends_dir.json");
+        let options = TsConfig::parse_file(&path)
+            .unwrap()
+            .compiler_options
+            .unwrap();
+
+        assert_eq!(options.strict, Some(true));
+        assert_eq!(options.jsx, Some(Jsx::React));
+    }
+
+    #[test]
+    fn parse_file_extending_without_extension() {
+        let path = Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap())
+            .join("test/tsconfig.extends_no_extension.json");
+        let options = TsConfig::parse_file(&path)
+            .unwrap()
+            .compiler_options
+            .unwrap();
+
+        assert_eq!(options.strict, Some(true));
+        assert_eq!(options.target, Some(Target::Es2019));
+        assert_eq!(options.declaration, Some(true));
+    }
+
+    #[test]
+    fn parse_file_lists_from_single_strings() {
+        let json = r#"{"files": "src/index.ts", "include": ["src"], "exclude": "**/*.test.ts"}"#;
         let config = TsConfig::parse_str(json).unwrap();
+        assert_eq!(config.files, Some(vec!["src/index.ts".to_string()]));
+        assert_eq!(config.include, Some(vec!["src".to_string()]));
+        assert_eq!(config.exclude, Some(vec!["**/*.test.ts".to_string()]));
+
+        let config = TsConfig::parse_str(r#"{"files": null}"#).unwrap();
+        assert_eq!(config.files, None);
+        assert!(TsConfig::parse_str(r#"{"files": 1}"#).is_err());
+    }
+
+    #[test]
+    fn distinguish_empty_files_from_absent_files() {
+        let config = TsConfig::parse_str(r#"{"files": []}"#).unwrap();
+        assert!(config.selects_no_files());
+
+        let config = TsConfig::parse_str(r#"{}"#).unwrap();
+        assert!(!config.selects_no_files());
+
+        let config = TsConfig::parse_str(r#"{"files": [], "include": ["src"]}"#).unwrap();
+        assert!(!config.selects_no_files());
+
+        let config = TsConfig::parse_str(r#"{"files": ["index.ts"]}"#).unwrap();
+        assert!(!config.selects_no_files());
+    }
+
+    #[test]
+    fn unknown_names_keep_their_casing() {
+        let json = r#"{"compilerOptions": {"target": "esNextX", "module": "ES2015", "lib": ["Dom.Future"]}}"#;
+        let options = TsConfig::parse_str(json).unwrap().compiler_options.unwrap();
+        assert_eq!(options.target, Some(Target::Other("esNextX".to_string())));
+        assert_eq!(options.module, Some(Module::Es2015));
         assert_eq!(
-            config
-                .compiler_options
+            options.lib,
+            Some(vec![Lib::Other("Dom.Future".to_string())])
+        );
+
+        let value = serde_json::to_value(&options).unwrap();
+        assert_eq!(value["target"], "esNextX");
+        assert_eq!(value["lib"][0], "Dom.Future");
+    }
+
+    #[test]
+    fn locate_build_info_file() {
+        let config_path = Path::new("/work/app/tsconfig.json");
+        let path = |json: &str| {
+            TsConfig::parse_str(json)
                 .unwrap()
-                .paths
+                .compiler_options
                 .unwrap()
-                .get("tests/*"),
-            Some(&vec!["tests/*".to_string()])
+                .effective_build_info_path(config_path)
+        };
+
+        assert_eq!(path(r#"{"compilerOptions": {"outDir": "dist"}}"#), None);
+        assert_eq!(
+            path(r#"{"compilerOptions": {"incremental": true}}"#),
+            Some(PathBuf::from("/work/app/tsconfig.tsbuildinfo"))
+        );
+        assert_eq!(
+            path(
+                r#"{"compilerOptions": {"incremental": true, "tsBuildInfoFile": "./.cache/build"}}"#
+            ),
+            Some(PathBuf::from("/work/app/.cache/build"))
+        );
+        assert_eq!(
+            path(r#"{"compilerOptions": {"composite": true, "outFile": "../out/bundle.js"}}"#),
+            Some(PathBuf::from("/work/out/bundle.tsbuildinfo"))
+        );
+        assert_eq!(
+            path(r#"{"compilerOptions": {"composite": true, "outDir": "lib", "rootDir": ".."}}"#),
+            Some(PathBuf::from("/work/app/lib/app/tsconfig.tsbuildinfo"))
+        );
+        // A rootDir beside the configuration, the usual layout, climbs back out of outDir.
+        assert_eq!(
+            path(r#"{"compilerOptions": {"composite": true, "outDir": "dist", "rootDir": "src"}}"#),
+            Some(PathBuf::from("/work/app/tsconfig.tsbuildinfo"))
+        );
+        assert_eq!(
+            path(
+                r#"{"compilerOptions": {"composite": true, "outDir": "build/out", "rootDir": "./src"}}"#
+            ),
+            Some(PathBuf::from("/work/app/build/tsconfig.tsbuildinfo"))
         );
     }
 
     #[test]
-    fn parse_empty() {
-        TsConfig::parse_str("{}").unwrap();
-        TsConfig::parse_str(r#"{"compilerOptions": {}}"#).unwrap();
+    fn reject_duplicate_keys_in_strict_mode() {
+        let duplicate = |json: &str| match TsConfig::parse_str_strict(json) {
+            Err(ConfigError::DuplicateKey(key)) => key,
+            other => panic!("expected a duplicate key, got {:?}", other),
+        };
+
+        assert_eq!(
+            duplicate(r#"{"compilerOptions": {"strict": true, "jsx": "react", "strict": false}}"#),
+            "compilerOptions.strict"
+        );
+        assert_eq!(duplicate(r#"{"files": [], "files": ["a.ts"]}"#), "files");
+        assert_eq!(
+            duplicate(r#"{"references": [{"path": "a"}, {"path": "b", "path": "c"}]}"#),
+            "references[1].path"
+        );
+
+        let json = r#"{"compilerOptions": {"paths": {"a": ["a"]}, "plugins": [{"name": "a"}, {"name": "b"}]}}"#;
+        assert!(TsConfig::parse_str_strict(json).is_ok());
     }
 
     #[test]
-    fn parse_default() {
-        let json = include_str!("../test/tsconfig.default.json");
-        TsConfig::parse_str(json).unwrap();
+    fn parse_new_line() {
+        let json = r#"{"compilerOptions": {"newLine": "lf"}}"#;
+        let options = TsConfig::parse_str(json).unwrap().compiler_options.unwrap();
+        assert_eq!(options.new_line, Some(NewLine::Lf));
+        assert_eq!(serde_json::to_value(&options).unwrap()["newLine"], "lf");
+
+        let json = r#"{"compilerOptions": {"newLine": "Crlf"}}"#;
+        let options = TsConfig::parse_str(json).unwrap().compiler_options.unwrap();
+        assert_eq!(options.new_line, Some(NewLine::Crlf));
+
+        assert!(TsConfig::parse_str(r#"{"compilerOptions": {"newLine": "cr"}}"#).is_err());
     }
 
     #[test]
-    fn parse_common_tsconfig() {
-        let json = include_str!("../test/tsconfig.common.json");
-        TsConfig::parse_str(json).unwrap();
+    fn round_trip_types_versions() {
+        let json = r#"{"typesVersions": {">=4.2": {"*": ["ts4.2/*"]}}}"#;
+        let config = TsConfig::parse_str(json).unwrap();
+        assert_eq!(
+            config.types_versions.as_ref().unwrap()[">=4.2"]["*"],
+            vec!["ts4.2/*".to_string()]
+        );
+
+        let value = serde_json::to_value(&config).unwrap();
+        assert_eq!(value, serde_json::from_str::<Value>(json).unwrap());
     }
 
     #[test]
-    fn parse_complete_tsconfig() {
-        let json = include_str!("../test/tsconfig.complete.json");
-        TsConfig::parse_str(json).unwrap();
+    fn parse_file_from_memory() {
+        struct MemoryResolver(HashMap<PathBuf, &'static str>);
+
+        impl ConfigResolver for MemoryResolver {
+            fn read(&self, path: &Path) -> Result<String> {
+                let path: PathBuf = path_segments(Path::new(""), &path.to_string_lossy())
+                    .iter()
+                    .collect();
+                match self.0.get(&path) {
+                    Some(contents) => Ok(contents.to_string()),
+                    None => Err(std::io::Error::from(std::io::ErrorKind::NotFound).into()),
+                }
+            }
+
+            fn canonicalize(&self, path: &Path) -> Result<PathBuf> {
+                Ok(path_segments(Path::new(""), &path.to_string_lossy())
+                    .iter()
+                    .collect())
+            }
+
+            fn is_file(&self, path: &Path) -> bool {
+                self.0.contains_key(path)
+            }
+
+            fn is_dir(&self, path: &Path) -> bool {
+                let path = self.canonicalize(path).unwrap();
+                self.0
+                    .keys()
+                    .any(|file| file != &path && file.starts_with(&path))
+            }
+        }
+
+        let resolver = MemoryResolver(
+            vec![
+                (
+                    PathBuf::from("/virtual/app/tsconfig.json"),
+                    r#"{"extends": ["../shared", "@org/config"], "compilerOptions": {"outDir": "dist"}}"#,
+                ),
+                (
+                    PathBuf::from("/virtual/shared/tsconfig.json"),
+                    r#"{"compilerOptions": {"strict": true, "jsx": "react"}}"#,
+                ),
+                (
+                    PathBuf::from("/virtual/node_modules/@org/config/package.json"),
+                    r#"{"tsconfig": "./base.json"}"#,
+                ),
+                (
+                    PathBuf::from("/virtual/node_modules/@org/config/base.json"),
+                    r#"{"compilerOptions": {"jsx": "preserve", "target": "es2020"}}"#,
+                ),
+            ]
+            .into_iter()
+            .collect(),
+        );
+
+        let config =
+            TsConfig::parse_with_resolver(&Path::new("/virtual/app/tsconfig.json"), &resolver)
+                .unwrap();
+        let options = config.compiler_options.unwrap();
+        assert_eq!(options.out_dir.as_deref(), Some("dist"));
+        assert_eq!(options.strict, Some(true));
+        assert_eq!(options.jsx, Some(Jsx::Preserve));
+        assert_eq!(options.target, Some(Target::Es2020));
     }
 
     #[test]
-    fn ignores_invalid_fields() {
-        let json = r#"{"bleep": true, "compilerOptions": {"someNewUnsupportedProperty": false}}"#;
-        TsConfig::parse_str(json).unwrap();
+    fn parse_multiline_arrays_with_trailing_commas() {
+        let path = Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap())
+            .join("test/tsconfig.multiline.json");
+        let config = TsConfig::parse_file(&path).unwrap();
+
+        assert_eq!(
+            config.include,
+            Some(vec!["src".to_string(), "types".to_string()])
+        );
+        let options = config.compiler_options.unwrap();
+        assert_eq!(options.lib, Some(vec![Lib::Es2020, Lib::Dom]));
+        assert_eq!(options.types, Some(vec!["node".to_string()]));
     }
 
     #[test]
-    fn ignores_dangling_commas() {
-        let json = r#"{"compilerOptions": {"noImplicitAny": false,"explainFiles": true,}}"#;
-        let cfg = TsConfig::parse_str(json).unwrap();
-        assert_eq!(cfg.compiler_options.unwrap().explain_files.unwrap(), true);
+    fn parse_module_suffixes() {
+        let json = r#"{"compilerOptions": {"moduleSuffixes": [".ios", ".native", ""]}}"#;
+        let options = TsConfig::parse_str(json).unwrap().compiler_options.unwrap();
+        assert_eq!(
+            options.module_suffixes,
+            Some(vec![
+                ".ios".to_string(),
+                ".native".to_string(),
+                "".to_string()
+            ])
+        );
+        assert!(options.unknown_options().is_empty());
+    }
 
-        let json = r#"{"compilerOptions": {"noImplicitAny": false,"explainFiles": true, }}"#;
-        let cfg = TsConfig::parse_str(json).unwrap();
-        assert_eq!(cfg.compiler_options.unwrap().explain_files.unwrap(), true);
+    #[cfg(feature = "preserve_order")]
+    #[test]
+    fn preserve_option_order() {
+        let json = r#"{"compilerOptions": {"target": "es2020", "strict": true, "jsx": "react", "allowJs": true}}"#;
+        let value = parse_to_value(json).unwrap();
+        let keys: Vec<&String> = value["compilerOptions"]
+            .as_object()
+            .unwrap()
+            .keys()
+            .collect();
 
-        let json = r#"{"compilerOptions": {"noImplicitAny": false,"explainFiles": true,
-    }}"#;
-        let cfg = TsConfig::parse_str(json).unwrap();
-        assert_eq!(cfg.compiler_options.unwrap().explain_files.unwrap(), true);
+        assert_eq!(keys, vec!["target", "strict", "jsx", "allowJs"]);
     }
 
     #[test]
-    fn merge_two_configs() {
-        let json_1 = r#"{"compilerOptions": {"jsx": "react", "noEmit": true,}}"#;
-        let json_2 = r#"{"compilerOptions": {"jsx": "preserve", "removeComments": true}}"#;
+    fn parse_watch_exclusions() {
+        let json = r#"{"watchOptions": {"excludeDirectories": ["**/node_modules"], "excludeFiles": ["build/fileWhichChangesOften.ts"]}}"#;
+        let watch_options = TsConfig::parse_str(json).unwrap().watch_options.unwrap();
 
-        let mut value1: Value = parse_to_value(json_1).unwrap();
-        let value2: Value = parse_to_value(json_2).unwrap();
+        assert_eq!(
+            watch_options.exclude_directories,
+            Some(vec!["**/node_modules".to_string()])
+        );
+        assert_eq!(
+            watch_options.exclude_files,
+            Some(vec!["build/fileWhichChangesOften.ts".to_string()])
+        );
+    }
 
-        merge(&mut value1, value2);
+    #[test]
+    fn collect_errors_across_extends_chain() {
+        let test_dir = Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap()).join("test");
+        let errors = TsConfig::check_file(&test_dir.join("tsconfig.extends_many_broken.json"));
 
-        let value: TsConfig = serde_json::from_value(value1).unwrap();
+        assert_eq!(errors.len(), 3, "{:?}", errors);
+        match &errors[0] {
+            ConfigError::InvalidExtends { path, source } => {
+                assert!(path.ends_with("b/tsconfig.broken.json"));
+                assert!(matches!(**source, ConfigError::InvalidJson { .. }));
+            }
+            other => panic!("expected an invalid base file, got {:?}", other),
+        }
+        match &errors[1] {
+            ConfigError::FileError { path, source } => {
+                assert!(path.ends_with("a/does_not_exist.json"));
+                assert!(matches!(**source, ConfigError::CouldNotFindFile(_)));
+            }
+            other => panic!("expected a file error, got {:?}", other),
+        }
+        match &errors[2] {
+            ConfigError::InvalidExtends { path, source } => {
+                assert!(path.ends_with("b/tsconfig.invalid_type.json"));
+                assert!(matches!(**source, ConfigError::ParseError(_)));
+            }
+            other => panic!("expected an invalid base file, got {:?}", other),
+        }
+    }
 
+    #[test]
+    fn parse_gzip_compressed_reader() {
+        use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+        use std::io::Write;
+
+        let json = r#"{"compilerOptions": {"strict": true, /* cached */ "outDir": "dist",},}"#;
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(json.as_bytes()).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let config = TsConfig::parse_reader(GzDecoder::new(&compressed[..])).unwrap();
+        let options = config.compiler_options.unwrap();
+        assert_eq!(options.strict, Some(true));
+        assert_eq!(options.out_dir, Some("dist".to_string()));
+
+        // A truncated stream is reported rather than parsed as far as it goes.
+        let truncated = &compressed[..compressed.len() / 2];
+        assert!(matches!(
+            TsConfig::parse_reader(GzDecoder::new(truncated)),
+            Err(ConfigError::CouldNotFindFile(_))
+        ));
+    }
+
+    #[test]
+    fn included_extensions_follow_flags() {
+        let options = CompilerOptions::default();
         assert_eq!(
-            value.clone().compiler_options.unwrap().jsx,
-            Some(Jsx::React)
+            options.included_extensions(),
+            vec![".ts", ".tsx", ".mts", ".cts"]
+        );
+
+        // An explicit allowJs wins over checkJs.
+        let options = CompilerOptions {
+            allow_js: Some(false),
+            check_js: Some(true),
+            ..CompilerOptions::default()
+        };
+        assert!(!options.included_extensions().contains(&".js"));
+
+        let options = CompilerOptions {
+            allow_arbitrary_extensions: Some(true),
+            resolve_json_module: Some(true),
+            ..CompilerOptions::default()
+        };
+        assert_eq!(
+            options.included_extensions(),
+            vec![".ts", ".tsx", ".mts", ".cts", ".json"]
         );
-        assert_eq!(value.clone().compiler_options.unwrap().no_emit, Some(true));
-        assert_eq!(value.compiler_options.unwrap().remove_comments, Some(true));
     }
 
     #[test]
-    fn parse_basic_file() {
-        let path = Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap())
-            .join("test/tsconfig.default.json");
-        let config = TsConfig::parse_file(&path).unwrap();
+    fn compare_targets() {
+        assert!(Target::Es2018 >= Target::Es2018);
+        assert!(Target::Es2015 < Target::Es2016);
+        assert!(Target::Es7 > Target::Es6);
+        assert!(Target::Es3 < Target::Es5);
 
+        // Aliases are ordered as the same edition.
         assert_eq!(
-            config.compiler_options.clone().unwrap().target,
-            Some(Target::Es5)
+            Target::Es6.partial_cmp(&Target::Es2015),
+            Some(Ordering::Equal)
         );
+        assert!(Target::Es6 >= Target::Es2015);
+        assert!(Target::Es2015 >= Target::Es6);
+        assert!(Target::Es7 > Target::Es2015);
+        assert!(Target::Es2016 < Target::Es2017);
+        assert_eq!(Target::Es6.at_least(&Target::Es2015), Some(true));
+        assert_eq!(Target::Es2015.at_least(&Target::Es6), Some(true));
+        assert_eq!(Target::Es7.at_least(&Target::Es2017), Some(false));
+        assert_eq!(Target::EsNext.at_least(&Target::Es2023), Some(true));
         assert_eq!(
-            config.compiler_options.clone().unwrap().module,
-            Some(Module::CommonJs)
+            Target::Es2018.at_least(&Target::Other("es2099".to_string())),
+            None
+        );
+        assert_eq!(
+            Target::Other("es1999".to_string()).partial_cmp(&Target::Es5),
+            None
+        );
+        assert_eq!(
+            Target::Other("x".to_string()).partial_cmp(&Target::Other("x".to_string())),
+            Some(Ordering::Equal)
         );
-        assert_eq!(config.compiler_options.unwrap().strict, Some(true));
     }
 
     #[test]
-    fn parse_inheriting_file() {
-        let path = Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap())
-            .join("test/tsconfig.inherits.json");
-        let config = TsConfig::parse_file(&path).unwrap();
+    fn references_climb_out_of_config_dir() {
+        let refs_dir = std::fs::canonicalize(
+            Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap()).join("test/refs"),
+        )
+        .unwrap();
+        // A relative directory that itself goes up and back down.
+        let config_dir = Path::new("test/refs/nested/../nested/app");
+        let config = TsConfig::parse_file(&config_dir.join("tsconfig.json")).unwrap();
 
+        let references = config.load_references(config_dir).unwrap();
+        let paths: Vec<&PathBuf> = references.iter().map(|(path, _)| path).collect();
         assert_eq!(
-            config
-                .compiler_options
-                .clone()
-                .unwrap()
-                .use_define_for_class_fields,
-            Some(false)
+            paths,
+            vec![
+                &refs_dir.join("core/tsconfig.json"),
+                &refs_dir.join("utils/tsconfig.build.json"),
+                &refs_dir.join("app/tsconfig.json")
+            ]
         );
 
+        // The project referenced under several spellings is built once.
         assert_eq!(
-            config.compiler_options.clone().unwrap().declaration,
-            Some(true)
+            config.build_order(config_dir).unwrap(),
+            vec![
+                refs_dir.join("core/tsconfig.json"),
+                refs_dir.join("utils/tsconfig.build.json"),
+                refs_dir.join("app/tsconfig.json")
+            ]
         );
+    }
+
+    #[test]
+    fn decide_whether_project_emits() {
+        let emits = |json: &str| TsConfig::parse_str(json).unwrap().emits_output();
+
+        assert!(emits("{}"));
+        assert!(emits(r#"{"files": ["index.ts"], "include": []}"#));
+        assert!(!emits(r#"{"files": []}"#));
+        assert!(!emits(r#"{"include": []}"#));
+        assert!(!emits(
+            r#"{"include": ["src"], "compilerOptions": {"noEmit": true}}"#
+        ));
+        assert!(emits(r#"{"compilerOptions": {"noEmit": false}}"#));
+        assert!(!emits(
+            r#"{"compilerOptions": {"emitDeclarationOnly": true}}"#
+        ));
+        assert!(emits(
+            r#"{"compilerOptions": {"emitDeclarationOnly": true, "composite": true}}"#
+        ));
+    }
+
+    #[test]
+    fn compiler_options_to_json_map() {
+        assert!(CompilerOptions::default().to_json_map().is_empty());
+
+        let json = r#"{"compilerOptions": {
+            "target": "ES2022",
+            "moduleResolution": "bundler",
+            "paths": {"@/*": ["src/*"]},
+            "exactOptionalPropertyTypes": false,
+            "someFutureOption": "x"
+        }}"#;
+        let options = TsConfig::parse_str(json).unwrap().compiler_options.unwrap();
+        let map = options.to_json_map();
 
+        let mut keys: Vec<&str> = map.keys().map(String::as_str).collect();
+        keys.sort_unstable();
         assert_eq!(
-            config.compiler_options.unwrap().trace_resolution,
-            Some(false)
+            keys,
+            vec![
+                "exactOptionalPropertyTypes",
+                "moduleResolution",
+                "paths",
+                "someFutureOption",
+                "target"
+            ]
         );
+        assert_eq!(map["exactOptionalPropertyTypes"], Value::Bool(false));
+        assert_eq!(map["paths"]["@/*"][0], Value::from("src/*"));
+
+        // The map is a faithful compilerOptions object.
+        let round_tripped = CompilerOptions::deserialize(Value::Object(map)).unwrap();
+        assert_eq!(round_tripped, options);
     }
 
     #[test]
-    fn parse_inheritance_chain() {
-        let path = Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap())
-            .join("test/a/tsconfig.inherits_again.json");
-        let config = TsConfig::parse_file(&path).unwrap();
+    fn list_included_files_from_relative_project_dir() {
+        // Tests run from the crate root.
+        let json = r#"{"exclude": ["target", ".git", "test/symlinks"]}"#;
+        let config = TsConfig::parse_str(json).unwrap();
+        let files = config.included_files(Path::new(".")).unwrap();
+        assert!(files.contains(&PathBuf::from("test/project/src/index.ts")));
+        assert!(files
+            .iter()
+            .all(|file| file.is_relative() && !file.starts_with(".")));
+
+        let json = r#"{"include": ["."], "exclude": ["target", ".git", "test/symlinks"]}"#;
+        let config = TsConfig::parse_str(json).unwrap();
+        assert_eq!(config.included_files(Path::new(".")).unwrap(), files);
 
+        let config = TsConfig::parse_str("{}").unwrap();
         assert_eq!(
-            config
-                .compiler_options
-                .clone()
-                .unwrap()
-                .use_define_for_class_fields,
-            Some(false)
+            config.included_files(Path::new("test/project")).unwrap(),
+            vec![
+                PathBuf::from("test/project/scripts/build.ts"),
+                PathBuf::from("test/project/src/components/App.tsx"),
+                PathBuf::from("test/project/src/globals.d.ts"),
+                PathBuf::from("test/project/src/index.ts")
+            ]
         );
+    }
+
+    #[test]
+    fn report_wrongly_typed_targets() {
+        let error = |options: Value| {
+            serde_json::from_value::<CompilerOptions>(options)
+                .unwrap_err()
+                .to_string()
+        };
 
         assert_eq!(
-            config.compiler_options.clone().unwrap().declaration,
-            Some(true)
+            error(serde_json::json!({"target": true})),
+            "invalid type: boolean `true`, expected a target name or ECMAScript edition number"
         );
-
         assert_eq!(
-            config.compiler_options.clone().unwrap().trace_resolution,
-            Some(false)
+            error(serde_json::json!({"target": 2015.5})),
+            "invalid type: floating point `2015.5`, expected a target name or ECMAScript edition number"
         );
-
-        assert_eq!(config.compiler_options.unwrap().jsx, Some(Jsx::ReactNative));
+        assert_eq!(
+            error(serde_json::json!({"module": ["es2015"]})),
+            "invalid type: sequence, expected a module name or ECMAScript edition number"
+        );
+        match TsConfig::parse_str(r#"{"compilerOptions": {"target": true}}"#) {
+            Err(ConfigError::ParseError(error)) => assert!(error
+                .to_string()
+                .starts_with("invalid type: boolean `true`, expected a target name")),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
     }
 }
@@ -0,0 +1 @@
+exports.main = 1;
@@ -0,0 +1 @@
+export const hidden = 1;